@@ -1,12 +1,29 @@
 use crate::components::brush::Brush;
 use crate::components::cell::Cell;
 use crate::components::charpicker::CharPicker;
+use crate::components::figlet::Font;
 use crate::components::history::{History, HistoryAction};
-use crate::components::input::InputCapture;
-use crate::components::layers::{LayerData, Layers};
+use crate::components::input::{InputCapture, InputMode};
+use crate::components::layers::{self, Floating, LayerData, LayerDrag, Layers};
 use crate::components::palette::Palette;
+use crate::components::pixels::{Corner, PixelMode};
+use crate::components::replace::{Replace, ReplaceScope};
+use crate::components::selection::{self, Selection};
+use crate::components::shapes;
+use crate::components::symmetry::Symmetry;
+use crate::components::text_entry::TextEntry;
+use crate::components::tools::{self, Tools};
+use crate::components::transform::Transform;
 use crate::ui::TOOLBOX_WIDTH;
 
+use ratatui::layout::Rect;
+
+/// Glyph sets the spray tool cycles through, the empty one uses the brush char
+const SPRAY_CHARS: [&str; 5] = ["", ".:*'", ",;'`", "·•∙", "░▒▓"];
+
+/// Ramps the shade tool cycles through
+const RAMPS: [&str; 3] = [" ░▒▓█", " .:-=+*#%@", " ▁▂▃▄▅▆▇█"];
+
 /// Application result type.
 pub type AppResult<T> = core::result::Result<T, Box<dyn std::error::Error>>;
 
@@ -20,6 +37,37 @@ pub struct App {
     pub palette: Palette,
     pub char_picker: CharPicker,
     pub brush: Brush,
+    /// Drawable part of the canvas in layer coordinates
+    pub canvas_area: Rect,
+    pub selection: Selection,
+    /// Text being typed with the text tool
+    pub text: Option<TextEntry>,
+    /// Banner fonts for the text tool
+    pub fonts: Vec<Font>,
+    /// Index of the font the text tool uses, plain text when [None]
+    pub font: Option<usize>,
+    /// Pixel of the cell the mouse is drawing on
+    pub corner: Corner,
+    /// Pixel of the cell the left button draws on, the right button takes the opposite one
+    pub picked_pixel: (u16, u16),
+    /// Glyphs the spray tool picks from, the brush char when empty
+    pub spray_chars: Vec<char>,
+    /// Glyphs the shade tool steps through, from light to dark
+    pub ramp: Vec<char>,
+    /// Where the ramp is being typed in, in front of which of its glyphs
+    pub ramp_caret: usize,
+    /// Shade tool steps down the ramp instead of up
+    pub shade_down: bool,
+    /// Cells the shade tool changed during the current stroke, as they were before it
+    pub shaded: LayerData,
+    /// Settings of the replace popup
+    pub replace: Replace,
+    /// Lines of the help popup scrolled past when it is taller than the terminal
+    pub help_scroll: u16,
+    /// Points placed with the polyline or bezier tool, in pixels when drawing on pixels
+    pub path: Vec<(u16, u16)>,
+    /// Index of the point of the path that is being dragged
+    pub grabbed: Option<usize>,
 }
 
 impl App {
@@ -28,6 +76,8 @@ impl App {
     pub fn new() -> Self {
         Self {
             running: true,
+            fonts: Font::bundled(),
+            ramp: RAMPS[0].chars().collect(),
             ..Default::default()
         }
     }
@@ -52,7 +102,7 @@ impl App {
     /// Removes a cell from the current layer and returns the cell value
     pub fn erase(&mut self, x: u16, y: u16) -> Cell {
         let layer = self.layers.current_layer_mut();
-        let old_cell = layer.data.remove(&(x, y)).unwrap_or_default();
+        let old_cell = layer.put((x, y), Cell::default());
 
         self.history.forget_redo();
 
@@ -62,6 +112,10 @@ impl App {
     pub fn draw(&mut self, x: u16, y: u16) -> LayerData {
         let x = x - TOOLBOX_WIDTH;
 
+        if self.pixel_mode() != PixelMode::Off {
+            return self.draw_pixels(x, y);
+        }
+
         let size = self.brush.size;
         let tool = self.brush.tool;
         let mut old_cells = LayerData::new();
@@ -77,6 +131,153 @@ impl App {
 
         self.layers.last_pos = Some((x, y));
 
+        self.draw_mirrored(old_cells)
+    }
+
+    /// Where the symmetry axes cross, in layer coordinates
+    pub fn symmetry_axis(&self) -> (u16, u16) {
+        let area = self.canvas_area;
+        self.brush
+            .axis
+            .unwrap_or((area.x + area.width / 2, area.y + area.height / 2))
+    }
+
+    /// Pixel mode of the current tool, tools that work with whole cells ignore the brush setting
+    pub fn pixel_mode(&self) -> PixelMode {
+        if self.brush.tool.is_cell_only() {
+            PixelMode::Off
+        } else {
+            self.brush.pixel_mode
+        }
+    }
+
+    /// Twice the position of the symmetry axes, counted in pixels when drawing on pixels
+    fn mirror_axis(&self) -> (i32, i32) {
+        let (ax, ay) = self.symmetry_axis();
+        let (width, height) = self.pixel_mode().size();
+
+        // The axes run through the middle of a cell
+        (
+            (2 * ax * width + width - 1) as i32,
+            (2 * ay * height + height - 1) as i32,
+        )
+    }
+
+    /// The pixel under the mouse, or the cell itself when not drawing on pixels
+    fn pixel_pos(&self, x: u16, y: u16) -> (u16, u16) {
+        let mode = self.pixel_mode();
+        let (width, height) = mode.size();
+        let (sub_x, sub_y) = mode.corner(self.corner, self.picked_pixel);
+
+        (x * width + sub_x, y * height + sub_y)
+    }
+
+    /// Draws with the current tool on pixels instead of cells
+    fn draw_pixels(&mut self, x: u16, y: u16) -> LayerData {
+        let pos = self.pixel_pos(x, y);
+        let size = self.brush.size;
+        let tool = self.brush.tool;
+
+        // Tools draw onto an empty, unlocked stand-in layer where every cell is a pixel,
+        // the locks are checked once the pixels are put back together into cells
+        let active = self.layers.current_layer_mut();
+        let layer = std::mem::take(&mut active.data);
+        let locks = (
+            std::mem::take(&mut active.locked),
+            std::mem::take(&mut active.alpha_locked),
+        );
+        let mut touched = LayerData::new();
+        for (px, py) in connect_points(pos, self.layers.last_pos) {
+            touched.extend(tool.draw(px, py, size, self));
+        }
+        let active = self.layers.current_layer_mut();
+        let drawn = std::mem::replace(&mut active.data, layer);
+        (active.locked, active.alpha_locked) = locks;
+
+        self.layers.last_pos = Some(pos);
+
+        // Pixels the tool erased are left empty
+        let mut pixels: LayerData = touched
+            .into_keys()
+            .map(|pos| (pos, drawn.get(&pos).copied().unwrap_or_default()))
+            .collect();
+        let mirrored = self.brush.symmetry.mirror(self.mirror_axis(), &pixels);
+        pixels.extend(mirrored);
+
+        let mut old_cells = LayerData::new();
+        for ((x, y), cell) in self.compose_pixels(&pixels) {
+            let old_cell = if cell == Cell::default() {
+                self.erase(x, y)
+            } else {
+                self.insert_at_cell(x, y, cell)
+            };
+            old_cells.insert((x, y), old_cell);
+        }
+
+        old_cells
+    }
+
+    /// Sets the pixels on the cells of the active layer, empty pixels are turned off.
+    /// Returns the changed cells, empty where a cell has no pixels left, leaving out the
+    /// ones the locks of the layer keep from changing.
+    fn compose_pixels(&mut self, pixels: &LayerData) -> LayerData {
+        let mode = self.pixel_mode();
+        let brush = self.brush;
+        let (width, height) = mode.size();
+
+        let active = self.layers.get_active_layer();
+        if active.locked {
+            return LayerData::new();
+        }
+        let alpha_locked = active.alpha_locked;
+        let layer = &active.data;
+        let mut cells = LayerData::new();
+
+        for (&(px, py), &pixel) in pixels {
+            let pos = (px / width, py / height);
+            let cell = cells
+                .get(&pos)
+                .or_else(|| layer.get(&pos))
+                .filter(|&&cell| cell != Cell::default())
+                .copied();
+
+            let on = pixel != Cell::default();
+            let new_cell = mode.paint(cell, (px % width, py % height), on, &brush);
+            cells.insert(pos, new_cell.unwrap_or_default());
+        }
+
+        // Alpha locked cells can't be drawn where there was nothing or be emptied
+        if alpha_locked {
+            cells.retain(|pos, cell| *cell != Cell::default() && layer.contains_key(pos));
+        }
+
+        cells
+    }
+
+    /// Repeats the changes to the drawn cells around the symmetry axes,
+    /// returns the old cells of both the drawn and the mirrored cells
+    fn draw_mirrored(&mut self, mut old_cells: LayerData) -> LayerData {
+        if self.brush.symmetry == Symmetry::Off {
+            return old_cells;
+        }
+
+        let layer = &self.layers.get_active_layer().data;
+        let drawn: LayerData = old_cells
+            .keys()
+            .map(|pos| (*pos, layer.get(pos).copied().unwrap_or_default()))
+            .collect();
+
+        let mirrored = self.brush.symmetry.mirror(self.mirror_axis(), &drawn);
+
+        for ((x, y), cell) in mirrored {
+            let old_cell = if cell == Cell::default() {
+                self.erase(x, y)
+            } else {
+                self.insert_at_cell(x, y, cell)
+            };
+            old_cells.entry((x, y)).or_insert(old_cell);
+        }
+
         old_cells
     }
 
@@ -84,17 +285,567 @@ impl App {
         let layer = self.layers.current_layer_mut();
         let new_cell = self.brush.as_cell();
 
-        let old_cell = layer.data.insert((x, y), new_cell).unwrap_or_default();
+        let old_cell = layer.put((x, y), new_cell);
 
         self.history.forget_redo();
 
         old_cell
     }
 
+    /// Cycles a setting of the current tool, `index` is the position of its label
+    pub fn next_option(&mut self, index: u8) {
+        match (self.brush.tool, index) {
+            (Tools::Text, 0) => {
+                self.font = match self.font {
+                    None if !self.fonts.is_empty() => Some(0),
+                    Some(i) if i + 1 < self.fonts.len() => Some(i + 1),
+                    _ => None,
+                }
+            }
+            (Tools::Spray, 1) => {
+                let current: String = self.spray_chars.iter().collect();
+                let next = SPRAY_CHARS
+                    .iter()
+                    .position(|&chars| chars == current)
+                    .map_or(0, |i| (i + 1) % SPRAY_CHARS.len());
+                self.spray_chars = SPRAY_CHARS[next].chars().collect();
+            }
+            (Tools::Spray, 2) => self.spray_chars.push(self.brush.char),
+            (Tools::Spray, 3) => {
+                self.spray_chars.pop();
+            }
+            (Tools::Shade, 0) => {
+                self.ramp_caret = self.ramp.len();
+                self.input_capture.change_mode(InputMode::Ramp);
+            }
+            // The brush char goes where the caret is, the ramp may hold glyphs that are hard to type
+            (Tools::Shade, 1) => {
+                if self.input_capture.mode != InputMode::Ramp {
+                    self.ramp_caret = self.ramp.len();
+                }
+                self.ramp.insert(self.ramp_caret, self.brush.char);
+                self.ramp_caret += 1;
+            }
+            (Tools::Shade, 2) => {
+                let current: String = self.ramp.iter().collect();
+                let next = RAMPS
+                    .iter()
+                    .position(|&ramp| ramp == current)
+                    .map_or(0, |i| (i + 1) % RAMPS.len());
+                self.ramp = RAMPS[next].chars().collect();
+                self.ramp_caret = self.ramp_caret.min(self.ramp.len());
+            }
+            _ => self.brush.next_option(index),
+        }
+    }
+
+    /// Labels for the settings of the current tool
+    pub fn option_labels(&self) -> Vec<String> {
+        match self.brush.tool {
+            Tools::Text => {
+                let font = self.font.and_then(|i| self.fonts.get(i));
+                vec![format!("Font: {}", font.map_or("Plain", |f| &f.name))]
+            }
+            Tools::Spray => {
+                let chars = if self.spray_chars.is_empty() {
+                    "Brush".into()
+                } else {
+                    self.spray_chars.iter().collect()
+                };
+                let mut labels = self.brush.option_labels();
+                labels.extend([chars, "+".into(), "-".into()]);
+                labels
+            }
+            Tools::Shade => {
+                let mut ramp: String = self.ramp.iter().collect();
+                // Room for the caret behind the last glyph
+                if self.input_capture.mode == InputMode::Ramp {
+                    ramp.push(' ');
+                }
+                vec![ramp, "+".into(), "Preset".into()]
+            }
+            _ => self.brush.option_labels(),
+        }
+    }
+
+    /// Sets the anchor point of a new shape
+    pub fn start_shape(&mut self, x: u16, y: u16) {
+        self.layers.anchor = Some(self.pixel_pos(x - TOOLBOX_WIDTH, y));
+        self.preview_shape(x, y);
+    }
+
+    /// Updates the preview of the shape being dragged out from the anchor point
+    pub fn preview_shape(&mut self, x: u16, y: u16) {
+        let Some(start) = self.layers.anchor else {
+            return;
+        };
+        let end = self.pixel_pos(x - TOOLBOX_WIDTH, y);
+        let brush = self.brush;
+        let pixels = self.pixel_mode() != PixelMode::Off;
+
+        let layer = if pixels {
+            &LayerData::new()
+        } else {
+            &self.layers.get_active_layer().data
+        };
+        let preview = match brush.tool {
+            Tools::Gradient => self.gradient(start, end),
+            tool => tool.shape(start, end, &brush, layer),
+        };
+
+        self.layers.preview = self.mirror_preview(preview);
+    }
+
+    /// Adds the mirror images to the cells of a preview and puts them onto pixels when drawing on pixels
+    fn mirror_preview(&mut self, mut preview: LayerData) -> LayerData {
+        let mirrored = self.brush.symmetry.mirror(self.mirror_axis(), &preview);
+        preview.extend(mirrored);
+
+        if self.pixel_mode() != PixelMode::Off {
+            preview = self.compose_pixels(&preview);
+        }
+
+        preview
+    }
+
+    /// Cells of a gradient dragged from `start` to `end`, spanning the selection when it starts
+    /// inside of it and the region the fill tool would fill otherwise
+    fn gradient(&mut self, start: (u16, u16), end: (u16, u16)) -> LayerData {
+        let region = match self.selection.area {
+            Some(area) if selection::contains(area, start) => selection::positions(area),
+            _ => tools::flood_region(
+                start,
+                &self.layers.get_active_layer().data,
+                self.brush.fill_match,
+                self.canvas_area,
+            ),
+        };
+
+        let layer = &self.layers.get_active_layer().data;
+        tools::gradient(&region, start, end, &self.brush, layer)
+    }
+
+    /// Writes the previewed shape to the current layer as a single history step
+    pub fn commit_shape(&mut self) {
+        self.layers.anchor = None;
+        self.commit_preview();
+    }
+
+    /// Places the next point of a polyline, or grabs a point of the curve being edited.
+    /// Clicking the last point of a polyline again finishes it, clicking away from a curve
+    /// finishes it and starts a new one.
+    pub fn click_path(&mut self, x: u16, y: u16) {
+        let pos = self.pixel_pos(x - TOOLBOX_WIDTH, y);
+
+        match self.brush.tool {
+            Tools::Polyline if self.path.last() == Some(&pos) => {
+                self.commit_path();
+                return;
+            }
+            Tools::Polyline => {
+                // The first point also starts the segment that gets dragged out from it
+                if self.path.is_empty() {
+                    self.path.push(pos);
+                }
+                self.path.push(pos);
+                self.grabbed = Some(self.path.len() - 1);
+            }
+            _ => {
+                self.grabbed = self.nearest_point(pos);
+                if self.grabbed.is_none() {
+                    self.commit_path();
+                    self.path = vec![pos, pos];
+                    self.grabbed = Some(1);
+                }
+            }
+        }
+
+        self.preview_path();
+    }
+
+    /// Moves the grabbed point of the path
+    pub fn drag_path(&mut self, x: u16, y: u16) {
+        let pos = self.pixel_pos(x - TOOLBOX_WIDTH, y);
+
+        if let Some(point) = self.grabbed.and_then(|i| self.path.get_mut(i)) {
+            *point = pos;
+            self.preview_path();
+        }
+    }
+
+    /// Lets go of the grabbed point, a curve that was just dragged out gets its control points
+    pub fn release_path(&mut self) {
+        self.grabbed = None;
+
+        if self.brush.tool != Tools::Bezier || self.path.len() != 2 {
+            return;
+        }
+
+        let (start, end) = (self.path[0], self.path[1]);
+        if start == end {
+            self.cancel_path();
+            return;
+        }
+
+        // Control points start out evenly spaced on the line between both ends
+        let controls = self.brush.curve.controls();
+        let point = |i: usize| {
+            let t = i as f64 / (controls + 1) as f64;
+            let lerp = |a: u16, b: u16| (a as f64 + (b as f64 - a as f64) * t).round() as u16;
+            (lerp(start.0, end.0), lerp(start.1, end.1))
+        };
+        self.path = (0..=controls + 1).map(point).collect();
+
+        self.preview_path();
+    }
+
+    /// The point of the path closest to `pos`, as long as it is at most a cell away
+    fn nearest_point(&self, pos: (u16, u16)) -> Option<usize> {
+        let (width, height) = self.pixel_mode().size();
+
+        self.path
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.0.abs_diff(pos.0) <= width && p.1.abs_diff(pos.1) <= height)
+            .min_by_key(|(_, p)| p.0.abs_diff(pos.0) + p.1.abs_diff(pos.1))
+            .map(|(i, _)| i)
+    }
+
+    /// Cells of the path, mirrored and put onto pixels like the other shapes
+    fn path_cells(&mut self) -> LayerData {
+        let cells = self.brush.tool.path(&self.path, &self.brush);
+        self.mirror_preview(cells)
+    }
+
+    /// Updates the preview of the path, the control points of a curve are marked
+    pub fn preview_path(&mut self) {
+        let mut preview = self.path_cells();
+
+        if self.brush.tool == Tools::Bezier && self.path.len() > 2 {
+            let (width, height) = self.pixel_mode().size();
+            let marker = Cell {
+                char: '◇',
+                ..self.brush.as_cell()
+            };
+            for &(x, y) in &self.path[1..self.path.len() - 1] {
+                preview.insert((x / width, y / height), marker);
+            }
+        }
+
+        self.layers.preview = preview;
+    }
+
+    /// Writes the path to the current layer as a single history step
+    pub fn commit_path(&mut self) {
+        if self.path.is_empty() {
+            return;
+        }
+
+        self.layers.preview = self.path_cells();
+        self.path.clear();
+        self.grabbed = None;
+        self.commit_preview();
+    }
+
+    /// Throws away the path without drawing it
+    pub fn cancel_path(&mut self) {
+        self.path.clear();
+        self.grabbed = None;
+        self.layers.preview.clear();
+    }
+
+    /// Places a caret to type at and switches to text input
+    pub fn start_text(&mut self, x: u16, y: u16) {
+        let font = self.font.and_then(|i| self.fonts.get(i)).cloned();
+        self.text = Some(TextEntry::new((x - TOOLBOX_WIDTH, y), font));
+        self.input_capture.change_mode(InputMode::Text);
+        self.preview_text();
+    }
+
+    /// Updates the preview of the text being typed
+    pub fn preview_text(&mut self) {
+        if let Some(text) = &self.text {
+            self.layers.preview = text.cells(&self.brush);
+        }
+    }
+
+    /// Writes the typed text to the current layer as a single history step
+    pub fn commit_text(&mut self) {
+        if self.text.take().is_some() {
+            self.commit_preview();
+        }
+        self.input_capture.exit();
+    }
+
+    /// Writes the preview to the current layer as a single history step
+    fn commit_preview(&mut self) {
+        let preview = std::mem::take(&mut self.layers.preview);
+        let mut old_cells = LayerData::new();
+
+        for ((x, y), cell) in preview {
+            old_cells.insert((x, y), self.insert_at_cell(x, y, cell));
+        }
+
+        let layer_id = self.layers.get_active_layer().id;
+        self.history.draw(layer_id, old_cells);
+    }
+
+    /// Grabbing inside the selection starts moving it, anywhere else starts a new one
+    pub fn start_selection(&mut self, x: u16, y: u16) {
+        let pos = (x - TOOLBOX_WIDTH, y);
+
+        if self.selection.contains(pos) {
+            self.selection.grab = Some(pos);
+        } else {
+            self.selection.anchor = Some(pos);
+            self.selection.area = Some(shapes::bounds(pos, pos));
+        }
+    }
+
+    /// Resizes the marquee or drags the selected cells along
+    pub fn drag_selection(&mut self, x: u16, y: u16) {
+        let pos = (x - TOOLBOX_WIDTH, y);
+
+        if let Some(anchor) = self.selection.anchor {
+            self.selection.area = Some(shapes::bounds(anchor, pos));
+            return;
+        }
+
+        let Some(grab) = self.selection.grab else {
+            return;
+        };
+
+        let (dx, dy) = (pos.0 as i32 - grab.0 as i32, pos.1 as i32 - grab.1 as i32);
+        if self.move_selection(dx, dy) {
+            self.selection.grab = Some(pos);
+        }
+    }
+
+    /// Lets go of the marquee, dragged cells are put down
+    pub fn end_selection(&mut self) {
+        self.selection.anchor = None;
+        if self.selection.grab.take().is_some() {
+            self.stamp_selection();
+        }
+    }
+
+    /// Moves the selected cells by (dx, dy), returns whether they moved.
+    /// The cells are lifted off the layer so they don't wipe out what they pass over,
+    /// see [App::stamp_selection].
+    pub fn move_selection(&mut self, dx: i32, dy: i32) -> bool {
+        let Some(area) = self.selection.area else {
+            return false;
+        };
+        let Some(new_area) = selection::translate(area, dx, dy) else {
+            return false;
+        };
+        if new_area == area || new_area.0 < 1 || new_area.1 < 1 {
+            return false;
+        }
+        if self.layers.get_active_layer().is_locked() {
+            return false;
+        }
+
+        if self.layers.floating.is_none() {
+            let layer = self.layers.current_layer_mut();
+            let cells = selection::cells(area, &layer.data);
+            layer.data.retain(|pos, _| !cells.contains_key(pos));
+
+            self.layers.floating = Some(Floating {
+                layer_id: layer.id,
+                cells: cells.clone(),
+                lifted: cells,
+            });
+        }
+
+        if let Some(floating) = &mut self.layers.floating {
+            (floating.cells, _) = layers::translate(&floating.cells, dx, dy, (u16::MAX, u16::MAX));
+        }
+        self.layers.queue_render();
+        self.selection.area = Some(new_area);
+
+        true
+    }
+
+    /// Puts the moved cells down where they are now as one history step,
+    /// the cells they cover are replaced only now
+    pub fn stamp_selection(&mut self) {
+        let Some(floating) = self.layers.floating.take() else {
+            return;
+        };
+
+        let layer = self.layers.get_layer_mut(floating.layer_id);
+        let mut old_cells = floating.lifted;
+        for (pos, cell) in floating.cells {
+            let old_cell = layer.put(pos, cell);
+            // Cells that were lifted from here already have their original value stored
+            old_cells.entry(pos).or_insert(old_cell);
+        }
+        old_cells.retain(|pos, old| layer.data.get(pos).copied().unwrap_or_default() != *old);
+
+        self.history.forget_redo();
+        self.history.draw(floating.layer_id, old_cells);
+    }
+
+    /// Flips or turns the selected cells, or the whole active layer when nothing is selected.
+    /// Returns the replaced cells, nothing changes on a locked layer.
+    pub fn transform_selection(&mut self, transform: Transform) -> LayerData {
+        let layer = self.layers.get_active_layer();
+        if layer.is_locked() {
+            return LayerData::new();
+        }
+        let layer = &layer.data;
+        let Some(area) = self
+            .selection
+            .area
+            .or_else(|| selection::bounding_area(layer))
+        else {
+            return LayerData::new();
+        };
+
+        let cells = selection::cells(area, layer);
+        let mut old_cells = LayerData::new();
+
+        for &(x, y) in cells.keys() {
+            old_cells.insert((x, y), self.erase(x, y));
+        }
+
+        for (pos, cell) in cells {
+            let (x, y) = transform.position(area, pos);
+            let cell = Cell {
+                char: transform.glyph(cell.char),
+                ..cell
+            };
+            let old_cell = self.insert_at_cell(x, y, cell);
+            old_cells.entry((x, y)).or_insert(old_cell);
+        }
+
+        if self.selection.area.is_some() {
+            self.selection.area = Some(transform.area(area));
+        }
+
+        old_cells
+    }
+
+    pub fn copy_selection(&mut self) {
+        let Some((left, top, right, bottom)) = self.selection.area else {
+            return;
+        };
+
+        let cells = self.selection.cells(&self.layers.get_active_layer().data);
+
+        self.selection.clipboard = cells
+            .into_iter()
+            .map(|((x, y), cell)| ((x - left, y - top), cell))
+            .collect();
+        self.selection.clipboard_size = (right - left + 1, bottom - top + 1);
+    }
+
+    /// Removes the selected cells, returns the removed cells
+    pub fn delete_selection(&mut self) -> LayerData {
+        let layer = self.layers.get_active_layer();
+        if layer.is_locked() {
+            return LayerData::new();
+        }
+        let cells = self.selection.cells(&layer.data);
+        let mut old_cells = LayerData::new();
+
+        for (x, y) in cells.into_keys() {
+            old_cells.insert((x, y), self.erase(x, y));
+        }
+
+        old_cells
+    }
+
+    pub fn cut_selection(&mut self) -> LayerData {
+        if self.layers.get_active_layer().is_locked() {
+            return LayerData::new();
+        }
+        self.copy_selection();
+        self.delete_selection()
+    }
+
+    /// Pastes the clipboard at the top left corner of the selection, or of the canvas
+    /// when nothing is selected. The pasted cells become the new selection.
+    pub fn paste_selection(&mut self) -> LayerData {
+        if self.layers.get_active_layer().is_locked() {
+            return LayerData::new();
+        }
+        let (left, top) = match self.selection.area {
+            Some((left, top, _, _)) => (left, top),
+            None => (self.canvas_area.x, self.canvas_area.y),
+        };
+        let (width, height) = self.selection.clipboard_size;
+        if width == 0 || height == 0 {
+            return LayerData::new();
+        }
+
+        let mut old_cells = LayerData::new();
+        for ((x, y), cell) in self.selection.clipboard.clone() {
+            let (x, y) = (left + x, top + y);
+            old_cells.insert((x, y), self.insert_at_cell(x, y, cell));
+        }
+
+        self.selection.area = Some((left, top, left + width - 1, top + height - 1));
+
+        old_cells
+    }
+
+    /// Rewrites every cell matching the replace settings, all layers change in one history step.
+    /// Returns false when there is no selection to replace in.
+    pub fn apply_replace(&mut self) -> bool {
+        let replace = self.replace;
+        let active = self.layers.get_active_layer().id;
+        let area = match replace.scope {
+            ReplaceScope::Selection => match self.selection.area {
+                Some(area) => Some(area),
+                None => {
+                    self.replace.no_selection = true;
+                    return false;
+                }
+            },
+            _ => None,
+        };
+
+        let mut actions = Vec::new();
+        for layer in self.layers.layers.iter_mut() {
+            let in_scope = match replace.scope {
+                ReplaceScope::Layer | ReplaceScope::Selection => layer.id == active,
+                ReplaceScope::Visible => layer.visible,
+            };
+            if !in_scope {
+                continue;
+            }
+
+            let changes: Vec<_> = layer
+                .data
+                .iter()
+                .filter(|(&pos, _)| area.is_none_or(|area| selection::contains(area, pos)))
+                .filter_map(|(&pos, &cell)| replace.apply(cell).map(|new_cell| (pos, new_cell)))
+                .collect();
+
+            let mut old_cells = LayerData::new();
+            for (pos, cell) in changes {
+                let old_cell = layer.put(pos, cell);
+                if layer.data.get(&pos) != Some(&old_cell) {
+                    old_cells.insert(pos, old_cell);
+                }
+            }
+            if !old_cells.is_empty() {
+                actions.push(HistoryAction::Draw(layer.id, old_cells));
+            }
+        }
+
+        self.history.forget_redo();
+        self.history.group(actions);
+        self.layers.queue_render();
+        true
+    }
+
     pub fn insert_at_cell(&mut self, x: u16, y: u16, cell: Cell) -> Cell {
         let layer = self.layers.current_layer_mut();
 
-        let old_cell = layer.data.insert((x, y), cell).unwrap_or_default();
+        let old_cell = layer.put((x, y), cell);
 
         self.history.forget_redo();
 
@@ -102,10 +853,18 @@ impl App {
     }
 
     pub fn undo(&mut self) {
-        let Some(mut action) = self.history.past.pop() else {
+        let Some(action) = self.history.past.pop() else {
             return;
         };
 
+        let action = self.undo_action(action);
+
+        self.history.future.push(action);
+        self.layers.queue_render();
+    }
+
+    /// Reverts an action, returns the action that redoes it
+    fn undo_action(&mut self, mut action: HistoryAction) -> HistoryAction {
         match action {
             HistoryAction::LayerAdded(id) => {
                 self.layers.remove_layer_by_id(id);
@@ -122,15 +881,28 @@ impl App {
                 action = HistoryAction::LayerRenamed(id, current_name);
             }
             HistoryAction::Draw(layer_id, ref draw_data) => {
-                let mut old_data = LayerData::new();
-                for (&pos, &cell) in draw_data {
-                    let cell_op = self.layers.get_layer_mut(layer_id).data.insert(pos, cell);
-
-                    if let Some(cell) = cell_op {
-                        old_data.insert(pos, cell);
-                    }
+                action = HistoryAction::Draw(layer_id, self.swap_cells(layer_id, draw_data));
+            }
+            HistoryAction::LayerMoved(layer_id, (dx, dy), ref clipped) => {
+                // Moving back never cuts anything off
+                self.layers
+                    .move_layer(layer_id, -dx, -dy, (u16::MAX, u16::MAX));
+                let layer = self.layers.get_layer_mut(layer_id);
+                layer
+                    .data
+                    .extend(clipped.iter().map(|(&pos, &cell)| (pos, cell)));
+            }
+            HistoryAction::LayerDuplicated(ref copy, _index) => {
+                self.layers.remove_layer_by_id(copy.id);
+            }
+            HistoryAction::LayersMerged(ref layers, ref merged, index) => {
+                self.layers.remove_layer_by_id(merged.id);
+                for (i, layer) in layers.iter().enumerate() {
+                    self.layers.insert_layer(layer.clone(), index + i);
                 }
-                action = HistoryAction::Draw(layer_id, old_data);
+            }
+            HistoryAction::LayersFlattened(ref layers, _) => {
+                self.layers.replace_all(layers.clone());
             }
             HistoryAction::LayerUp(layer_id) => {
                 let _ = self.layers.move_layer_down_by_id(layer_id);
@@ -138,17 +910,33 @@ impl App {
             HistoryAction::LayerDown(layer_id) => {
                 let _ = self.layers.move_layer_up_by_id(layer_id);
             }
+            HistoryAction::Group(actions) => {
+                let mut redo: Vec<HistoryAction> = actions
+                    .into_iter()
+                    .rev()
+                    .map(|a| self.undo_action(a))
+                    .collect();
+                redo.reverse();
+                action = HistoryAction::Group(redo);
+            }
         }
 
-        self.history.future.push(action);
-        self.layers.queue_render();
+        action
     }
 
     pub fn redo(&mut self) {
-        let Some(mut action) = self.history.future.pop() else {
+        let Some(action) = self.history.future.pop() else {
             return;
         };
 
+        let action = self.redo_action(action);
+
+        self.history.past.push(action);
+        self.layers.queue_render();
+    }
+
+    /// Applies an undone action again, returns the action that undoes it
+    fn redo_action(&mut self, mut action: HistoryAction) -> HistoryAction {
         match action {
             HistoryAction::LayerAdded(id) => self.layers.add_layer_with_id(id),
             HistoryAction::LayerRemoved(ref layer, _index) => {
@@ -162,15 +950,23 @@ impl App {
                 action = HistoryAction::LayerRenamed(id, current_name);
             }
             HistoryAction::Draw(layer_id, ref draw_data) => {
-                let mut old_data = LayerData::new();
-                for (&pos, &cell) in draw_data {
-                    let cell_op = self.layers.get_layer_mut(layer_id).data.insert(pos, cell);
-
-                    if let Some(cell) = cell_op {
-                        old_data.insert(pos, cell);
-                    }
+                action = HistoryAction::Draw(layer_id, self.swap_cells(layer_id, draw_data));
+            }
+            HistoryAction::LayerMoved(layer_id, (dx, dy), _) => {
+                let clipped = self.layers.move_layer(layer_id, dx, dy, self.canvas_max());
+                action = HistoryAction::LayerMoved(layer_id, (dx, dy), clipped);
+            }
+            HistoryAction::LayerDuplicated(ref copy, index) => {
+                self.layers.insert_layer(copy.clone(), index);
+            }
+            HistoryAction::LayersMerged(ref layers, ref merged, index) => {
+                for layer in layers {
+                    self.layers.remove_layer_by_id(layer.id);
                 }
-                action = HistoryAction::Draw(layer_id, old_data);
+                self.layers.insert_layer(merged.clone(), index);
+            }
+            HistoryAction::LayersFlattened(_, ref flat) => {
+                self.layers.replace_all(vec![flat.clone()]);
             }
             HistoryAction::LayerUp(layer_id) => {
                 self.layers.move_layer_up_by_id(layer_id);
@@ -178,10 +974,26 @@ impl App {
             HistoryAction::LayerDown(layer_id) => {
                 self.layers.move_layer_down_by_id(layer_id);
             }
+            HistoryAction::Group(actions) => {
+                let undo = actions.into_iter().map(|a| self.redo_action(a)).collect();
+                action = HistoryAction::Group(undo);
+            }
         }
 
-        self.history.past.push(action);
-        self.layers.queue_render();
+        action
+    }
+
+    /// Writes `data` to a layer, returns the cells that were there before
+    fn swap_cells(&mut self, layer_id: u32, data: &LayerData) -> LayerData {
+        let mut old_data = LayerData::new();
+        for (&pos, &cell) in data {
+            let cell_op = self.layers.get_layer_mut(layer_id).data.insert(pos, cell);
+
+            if let Some(cell) = cell_op {
+                old_data.insert(pos, cell);
+            }
+        }
+        old_data
     }
 
     pub fn remove_active_layer(&mut self) {
@@ -189,6 +1001,96 @@ impl App {
         self.history.remove_layer(layer, index);
     }
 
+    /// The last cell of the canvas, cells moved past it are cut off
+    fn canvas_max(&self) -> (u16, u16) {
+        let area = self.canvas_area;
+        (
+            area.right().saturating_sub(1),
+            area.bottom().saturating_sub(1),
+        )
+    }
+
+    /// Moves all of the active layer by (dx, dy) as one history step
+    pub fn move_layer(&mut self, dx: i32, dy: i32) {
+        let id = self.layers.get_active_layer().id;
+        self.move_layer_by_id(id, (dx, dy));
+    }
+
+    fn move_layer_by_id(&mut self, id: u32, (dx, dy): (i32, i32)) {
+        let Some(layer) = self.layers.layers.iter().find(|l| l.id == id) else {
+            return;
+        };
+        if layer.is_locked() || layer.data.is_empty() {
+            return;
+        }
+
+        let clipped = self.layers.move_layer(id, dx, dy, self.canvas_max());
+        self.history.forget_redo();
+        self.history.move_layer(id, (dx, dy), clipped);
+    }
+
+    /// Grabs the active layer to drag it around with the move tool
+    pub fn start_move(&mut self, x: u16, y: u16) {
+        let layer = self.layers.get_active_layer();
+        if layer.is_locked() {
+            return;
+        }
+        self.layers.dragged = Some(LayerDrag {
+            layer_id: layer.id,
+            start: (x, y),
+            offset: (0, 0),
+        });
+    }
+
+    /// Shows the grabbed layer moved along with the mouse
+    pub fn drag_move(&mut self, x: u16, y: u16) {
+        let Some(drag) = &mut self.layers.dragged else {
+            return;
+        };
+        drag.offset = (
+            x as i32 - drag.start.0 as i32,
+            y as i32 - drag.start.1 as i32,
+        );
+        self.layers.queue_render();
+    }
+
+    /// Lets go of the grabbed layer, moving it as far as it was shown in one history step
+    pub fn end_move(&mut self) {
+        let Some(drag) = self.layers.dragged.take() else {
+            return;
+        };
+
+        self.layers.queue_render();
+        if drag.offset != (0, 0) {
+            self.move_layer_by_id(drag.layer_id, drag.offset);
+        }
+    }
+
+    pub fn duplicate_active_layer(&mut self) {
+        let (copy, index) = self.layers.duplicate_active_layer();
+        self.history.duplicate_layer(copy, index);
+    }
+
+    pub fn merge_down(&mut self) {
+        #[cfg(debug_assertions)]
+        let before = self.layers.render();
+
+        if let Some((layers, merged, index)) = self.layers.merge_down() {
+            #[cfg(debug_assertions)]
+            assert!(
+                before == self.layers.render(),
+                "merging down changed the picture"
+            );
+            self.history.merge_layers(layers, merged, index);
+        }
+    }
+
+    pub fn flatten(&mut self) {
+        if let Some((layers, flat)) = self.layers.flatten() {
+            self.history.flatten_layers(layers, flat);
+        }
+    }
+
     pub fn apply_rename(&mut self) -> Option<()> {
         let new_name = self.input_capture.text_area.get()?;
         let (id, old_name) = self.layers.rename_active_layer(new_name);
@@ -201,11 +1103,12 @@ impl App {
         self.history = History::default();
         self.palette = Palette::default();
         self.brush = Brush::default();
+        self.selection.clear();
         self.layers.queue_render();
     }
 }
 
-fn connect_points(start: (u16, u16), end: Option<(u16, u16)>) -> Vec<(u16, u16)> {
+pub fn connect_points(start: (u16, u16), end: Option<(u16, u16)>) -> Vec<(u16, u16)> {
     let Some(end) = end else {
         return vec![start];
     };
@@ -0,0 +1,197 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Thickness of a line leaving a box drawing glyph
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Weight {
+    #[default]
+    None,
+    Light,
+    Heavy,
+    Double,
+}
+
+use Weight::{Double as D, Heavy as H, Light as L, None as O};
+
+/// Lines leaving a glyph in the order up, right, down, left
+pub type Arms = [Weight; 4];
+
+pub const UP: usize = 0;
+pub const RIGHT: usize = 1;
+pub const DOWN: usize = 2;
+pub const LEFT: usize = 3;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameStyle {
+    #[default]
+    Light,
+    Heavy,
+    Double,
+    Rounded,
+    Ascii,
+}
+
+impl fmt::Display for FrameStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FrameStyle {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Light => Self::Heavy,
+            Self::Heavy => Self::Double,
+            Self::Double => Self::Rounded,
+            Self::Rounded => Self::Ascii,
+            Self::Ascii => Self::Light,
+        }
+    }
+
+    /// Weight of the lines drawn with this style
+    pub const fn weight(&self) -> Weight {
+        match self {
+            Self::Heavy => H,
+            Self::Double => D,
+            Self::Light | Self::Rounded | Self::Ascii => L,
+        }
+    }
+}
+
+/// Glyph put at an end of a connector
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Arrowhead {
+    #[default]
+    None,
+    Arrow,
+    Triangle,
+}
+
+impl Arrowhead {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::None => Self::Arrow,
+            Self::Arrow => Self::Triangle,
+            Self::Triangle => Self::None,
+        }
+    }
+
+    /// The head pointing towards `side`, [None] when there is no head
+    pub const fn glyph(&self, side: usize) -> Option<char> {
+        let heads = match self {
+            Self::None => return None,
+            Self::Arrow => ['↑', '→', '↓', '←'],
+            Self::Triangle => ['▲', '▶', '▼', '◀'],
+        };
+        Some(heads[side])
+    }
+}
+
+/// Glyphs that can be produced, every combination of arms maps to at most one of them
+#[rustfmt::skip]
+const GLYPHS: &[(char, Arms)] = &[
+    ('─', [O, L, O, L]), ('━', [O, H, O, H]), ('│', [L, O, L, O]), ('┃', [H, O, H, O]),
+    ('┌', [O, L, L, O]), ('┍', [O, H, L, O]), ('┎', [O, L, H, O]), ('┏', [O, H, H, O]),
+    ('┐', [O, O, L, L]), ('┑', [O, O, L, H]), ('┒', [O, O, H, L]), ('┓', [O, O, H, H]),
+    ('└', [L, L, O, O]), ('┕', [L, H, O, O]), ('┖', [H, L, O, O]), ('┗', [H, H, O, O]),
+    ('┘', [L, O, O, L]), ('┙', [L, O, O, H]), ('┚', [H, O, O, L]), ('┛', [H, O, O, H]),
+    ('├', [L, L, L, O]), ('┝', [L, H, L, O]), ('┞', [H, L, L, O]), ('┟', [L, L, H, O]),
+    ('┠', [H, L, H, O]), ('┡', [H, H, L, O]), ('┢', [L, H, H, O]), ('┣', [H, H, H, O]),
+    ('┤', [L, O, L, L]), ('┥', [L, O, L, H]), ('┦', [H, O, L, L]), ('┧', [L, O, H, L]),
+    ('┨', [H, O, H, L]), ('┩', [H, O, L, H]), ('┪', [L, O, H, H]), ('┫', [H, O, H, H]),
+    ('┬', [O, L, L, L]), ('┭', [O, L, L, H]), ('┮', [O, H, L, L]), ('┯', [O, H, L, H]),
+    ('┰', [O, L, H, L]), ('┱', [O, L, H, H]), ('┲', [O, H, H, L]), ('┳', [O, H, H, H]),
+    ('┴', [L, L, O, L]), ('┵', [L, L, O, H]), ('┶', [L, H, O, L]), ('┷', [L, H, O, H]),
+    ('┸', [H, L, O, L]), ('┹', [H, L, O, H]), ('┺', [H, H, O, L]), ('┻', [H, H, O, H]),
+    ('┼', [L, L, L, L]), ('┽', [L, L, L, H]), ('┾', [L, H, L, L]), ('┿', [L, H, L, H]),
+    ('╀', [H, L, L, L]), ('╁', [L, L, H, L]), ('╂', [H, L, H, L]), ('╃', [H, L, L, H]),
+    ('╄', [H, H, L, L]), ('╅', [L, L, H, H]), ('╆', [L, H, H, L]), ('╇', [H, H, L, H]),
+    ('╈', [L, H, H, H]), ('╉', [H, L, H, H]), ('╊', [H, H, H, L]), ('╋', [H, H, H, H]),
+    ('═', [O, D, O, D]), ('║', [D, O, D, O]), ('╒', [O, D, L, O]), ('╓', [O, L, D, O]),
+    ('╔', [O, D, D, O]), ('╕', [O, O, L, D]), ('╖', [O, O, D, L]), ('╗', [O, O, D, D]),
+    ('╘', [L, D, O, O]), ('╙', [D, L, O, O]), ('╚', [D, D, O, O]), ('╛', [L, O, O, D]),
+    ('╜', [D, O, O, L]), ('╝', [D, O, O, D]), ('╞', [L, D, L, O]), ('╟', [D, L, D, O]),
+    ('╠', [D, D, D, O]), ('╡', [L, O, L, D]), ('╢', [D, O, D, L]), ('╣', [D, O, D, D]),
+    ('╤', [O, D, L, D]), ('╥', [O, L, D, L]), ('╦', [O, D, D, D]), ('╧', [L, D, O, D]),
+    ('╨', [D, L, O, L]), ('╩', [D, D, O, D]), ('╪', [L, D, L, D]), ('╫', [D, L, D, L]),
+    ('╬', [D, D, D, D]), ('╴', [O, O, O, L]), ('╵', [L, O, O, O]), ('╶', [O, L, O, O]),
+    ('╷', [O, O, L, O]), ('╸', [O, O, O, H]), ('╹', [H, O, O, O]), ('╺', [O, H, O, O]),
+    ('╻', [O, O, H, O]), ('╼', [O, H, O, L]), ('╽', [L, O, H, O]), ('╾', [O, L, O, H]),
+    ('╿', [H, O, L, O]),
+];
+
+/// Glyphs that are understood when merging but never produced
+#[rustfmt::skip]
+const ALIASES: &[(char, Arms)] = &[
+    ('┄', [O, L, O, L]), ('┅', [O, H, O, H]), ('┆', [L, O, L, O]), ('┇', [H, O, H, O]),
+    ('┈', [O, L, O, L]), ('┉', [O, H, O, H]), ('┊', [L, O, L, O]), ('┋', [H, O, H, O]),
+    ('╌', [O, L, O, L]), ('╍', [O, H, O, H]), ('╎', [L, O, L, O]), ('╏', [H, O, H, O]),
+    ('╭', [O, L, L, O]), ('╮', [O, O, L, L]), ('╯', [L, O, O, L]), ('╰', [L, L, O, O]),
+    ('-', [O, L, O, L]), ('|', [L, O, L, O]), ('+', [L, L, L, L]),
+];
+
+/// Lines leaving the glyph, or [None] if it isn't a box drawing glyph
+pub fn arms(c: char) -> Option<Arms> {
+    GLYPHS
+        .iter()
+        .chain(ALIASES.iter())
+        .find(|&&(glyph, _)| glyph == c)
+        .map(|&(_, arms)| arms)
+}
+
+/// Finds the glyph for a set of arms in the given style
+///
+/// Unicode has no glyph for some combinations (e.g. heavy and double lines meeting),
+/// those get their heavy and then their double lines drawn light instead
+pub fn glyph(arms: Arms, style: FrameStyle) -> Option<char> {
+    if arms == [O; 4] {
+        return None;
+    }
+
+    if style == FrameStyle::Ascii {
+        let horizontal = arms[LEFT] != O || arms[RIGHT] != O;
+        let vertical = arms[UP] != O || arms[DOWN] != O;
+        return Some(match (horizontal, vertical) {
+            (true, false) => '-',
+            (false, true) => '|',
+            _ => '+',
+        });
+    }
+
+    let lighten = |from: Weight, arms: Arms| arms.map(|w| if w == from { L } else { w });
+    let candidates = [
+        arms,
+        lighten(H, arms),
+        lighten(D, arms),
+        lighten(D, lighten(H, arms)),
+    ];
+
+    let c = candidates
+        .iter()
+        .find_map(|&arms| GLYPHS.iter().find(|&&(_, a)| a == arms))
+        .map(|&(c, _)| c)?;
+
+    Some(match (style, c) {
+        (FrameStyle::Rounded, '┌') => '╭',
+        (FrameStyle::Rounded, '┐') => '╮',
+        (FrameStyle::Rounded, '┘') => '╯',
+        (FrameStyle::Rounded, '└') => '╰',
+        (_, c) => c,
+    })
+}
+
+/// Combines new arms with the ones of an existing glyph, new lines take priority
+pub fn merge(new: Arms, old: char) -> Arms {
+    let Some(old) = arms(old) else {
+        return new;
+    };
+
+    let mut out = new;
+    for (side, weight) in out.iter_mut().enumerate() {
+        if *weight == O {
+            *weight = old[side];
+        }
+    }
+    out
+}
@@ -1,7 +1,12 @@
 use ratatui::style::{Color, Style};
 use serde::{Deserialize, Serialize};
 
-use super::{cell::Cell, tools::Tools};
+use super::box_drawing::{Arrowhead, FrameStyle, LEFT, RIGHT};
+use super::cell::Cell;
+use super::palette::Quantize;
+use super::pixels::PixelMode;
+use super::symmetry::Symmetry;
+use super::tools::{Curve, Density, FillMatch, PickSource, PickTarget, Tools};
 
 const BRUSH_MIN: u16 = 1;
 const BRUSH_MAX: u16 = 21;
@@ -14,6 +19,60 @@ pub struct Brush {
     pub size: u16,
     pub char: char,
     pub tool: Tools,
+
+    /// Line tool picks its glyph from the slope instead of using the brush char
+    #[serde(default)]
+    pub line_glyphs: bool,
+
+    /// Border glyphs used by the frame tool
+    #[serde(default)]
+    pub frame_style: FrameStyle,
+
+    /// Heads at the start and the end of the connector tool
+    #[serde(default)]
+    pub start_head: Arrowhead,
+    #[serde(default = "end_head")]
+    pub end_head: Arrowhead,
+
+    /// What the fill tool compares to find the region
+    #[serde(default)]
+    pub fill_match: FillMatch,
+
+    /// Where the picker tool samples cells from
+    #[serde(default)]
+    pub pick_source: PickSource,
+
+    /// Which parts of the sampled cell the picker tool takes
+    #[serde(default)]
+    pub pick_target: PickTarget,
+
+    /// Share of the cells under the spray tool that get drawn
+    #[serde(default)]
+    pub spray_density: Density,
+
+    /// Palette the gradient tool rounds its colors to
+    #[serde(default)]
+    pub quantize: Quantize,
+
+    /// Gradient tool blends the brush colors with shading glyphs instead of mixing them
+    #[serde(default)]
+    pub dither: bool,
+
+    /// Number of control points of the bezier tool
+    #[serde(default)]
+    pub curve: Curve,
+
+    /// Axes everything drawn gets mirrored around
+    #[serde(default)]
+    pub symmetry: Symmetry,
+
+    /// Where the symmetry axes cross, the middle of the canvas when [None]
+    #[serde(default)]
+    pub axis: Option<(u16, u16)>,
+
+    /// Draws on pixels smaller than a cell instead of whole cells
+    #[serde(default)]
+    pub pixel_mode: PixelMode,
 }
 
 impl Default for Brush {
@@ -24,10 +83,29 @@ impl Default for Brush {
             bg: Color::White,
             char: '░',
             tool: Tools::default(),
+            line_glyphs: false,
+            frame_style: FrameStyle::default(),
+            start_head: Arrowhead::None,
+            end_head: end_head(),
+            fill_match: FillMatch::default(),
+            pick_source: PickSource::default(),
+            pick_target: PickTarget::default(),
+            spray_density: Density::default(),
+            quantize: Quantize::default(),
+            dither: false,
+            curve: Curve::default(),
+            symmetry: Symmetry::default(),
+            axis: None,
+            pixel_mode: PixelMode::default(),
         }
     }
 }
 
+/// Connectors point towards where they end by default
+const fn end_head() -> Arrowhead {
+    Arrowhead::Arrow
+}
+
 impl Brush {
     pub const fn style(&self) -> Style {
         Style::new().fg(self.fg).bg(self.bg)
@@ -50,4 +128,70 @@ impl Brush {
     pub fn up(&mut self, val: u16) {
         self.size = (self.size + val).min(BRUSH_MAX);
     }
+
+    /// Cycles a setting of the current tool, `index` is the position of its label
+    pub fn next_option(&mut self, index: u8) {
+        match (self.tool, index) {
+            (Tools::Line, 0) => self.line_glyphs = !self.line_glyphs,
+            (Tools::Frame, 0) => self.frame_style = self.frame_style.next(),
+            (Tools::Connector, 0) => self.start_head = self.start_head.next(),
+            (Tools::Connector, 1) => self.end_head = self.end_head.next(),
+            (Tools::Connector, 2) => self.frame_style = self.frame_style.next(),
+            (Tools::Fill, 0) => self.fill_match = self.fill_match.next(),
+            (Tools::Picker, 0) => self.pick_source = self.pick_source.next(),
+            (Tools::Picker, 1) => self.pick_target = self.pick_target.next(),
+            (Tools::Spray, 0) => self.spray_density = self.spray_density.next(),
+            (Tools::Gradient, 0) => self.quantize = self.quantize.next(),
+            (Tools::Gradient, 1) => self.dither = !self.dither,
+            (Tools::Bezier, 0) => self.curve = self.curve.next(),
+            _ => {}
+        }
+    }
+
+    /// Gradients can't start or end at an unset color, there is no knowing what it looks like
+    pub fn has_unset_color(&self) -> bool {
+        self.fg == Color::Reset || self.bg == Color::Reset
+    }
+
+    /// Labels for the settings of the current tool
+    pub fn option_labels(&self) -> Vec<String> {
+        match self.tool {
+            Tools::Line => vec![format!(
+                "Glyph: {}",
+                if self.line_glyphs { "Auto" } else { "Brush" }
+            )],
+            Tools::Frame => vec![format!("Style: {}", self.frame_style)],
+            Tools::Connector => vec![
+                format!("{}─", self.start_head.glyph(LEFT).unwrap_or('─')),
+                format!("─{}", self.end_head.glyph(RIGHT).unwrap_or('─')),
+                self.frame_style.to_string(),
+            ],
+            Tools::Fill => vec![format!("Match: {}", self.fill_match)],
+            Tools::Picker => vec![
+                self.pick_source.to_string(),
+                format!("Pick: {}", self.pick_target),
+            ],
+            Tools::Spray => vec![self.spray_density.to_string()],
+            Tools::Gradient => vec![
+                self.quantize.to_string(),
+                format!("Dither: {}", if self.dither { "On" } else { "Off" }),
+            ],
+            Tools::Bezier => vec![self.curve.to_string()],
+            _ => vec![],
+        }
+    }
+
+    /// Takes on the parts of the cell chosen by the picker settings
+    pub fn pick(&mut self, cell: Cell) {
+        match self.pick_target {
+            PickTarget::All => {
+                self.fg = cell.fg;
+                self.bg = cell.bg;
+                self.char = cell.char;
+            }
+            PickTarget::Fg => self.fg = cell.fg,
+            PickTarget::Bg => self.bg = cell.bg,
+            PickTarget::Char => self.char = cell.char,
+        }
+    }
 }
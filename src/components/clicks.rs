@@ -1,5 +1,7 @@
 use ratatui::style::Color;
 
+use super::replace::ReplaceScope;
+use super::tools::FillMatch;
 use super::{input::color::TextFocus, tools::Tools};
 
 #[repr(u8)]
@@ -15,6 +17,7 @@ pub enum ClickAction {
     Save(PopupBoxAction),
     Exit(PopupBoxAction),
     PickColor(PickAction),
+    Replace(ReplaceAction),
 }
 
 #[repr(u8)]
@@ -22,6 +25,7 @@ pub enum ClickAction {
 pub enum Increment {
     CharPicker,
     BrushSize,
+    ToolOption(u8),
 }
 
 #[repr(u8)]
@@ -50,6 +54,13 @@ pub enum LayerAction {
     MoveUp,
     MoveDown,
     ToggleVis(u8),
+    CycleBlend(u8),
+    Opacity(u8),
+    ToggleLock(u8),
+    ToggleAlphaLock(u8),
+    Duplicate,
+    MergeDown,
+    Flatten,
 }
 
 #[repr(u8)]
@@ -74,3 +85,18 @@ pub enum PickAction {
     Exit,
     Nothing,
 }
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplaceAction {
+    By(FillMatch),
+    Scope(ReplaceScope),
+    /// Take the cell to look for from the brush
+    FindBrush,
+    /// Take the replacement from the brush
+    WithBrush,
+    Swap,
+    Accept,
+    Deny,
+    Nothing,
+}
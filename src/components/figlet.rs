@@ -0,0 +1,268 @@
+use std::fmt;
+
+/// Fonts that ship with the binary
+const BUNDLED: [(&str, &str); 3] = [
+    ("Block", include_str!("../../assets/fonts/block.flf")),
+    ("Mini", include_str!("../../assets/fonts/mini.flf")),
+    ("Hash", include_str!("../../assets/fonts/hash.flf")),
+];
+
+/// Codes of the chars every font has after the printable ASCII ones
+const DEUTSCH: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+// Layout bits of the header
+const SMUSH_EQUAL: u32 = 1;
+const SMUSH_LOWLINE: u32 = 2;
+const SMUSH_HIERARCHY: u32 = 4;
+const SMUSH_PAIR: u32 = 8;
+const SMUSH_BIGX: u32 = 16;
+const SMUSH_HARDBLANK: u32 = 32;
+const FITTING: u32 = 64;
+const SMUSHING: u32 = 128;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FontError {
+    /// Missing or malformed `flf2a` header
+    NotAFont,
+    /// The file ends before all of the required chars
+    Truncated,
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotAFont => write!(f, "not a FIGlet font"),
+            Self::Truncated => write!(f, "font file is incomplete"),
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+/// A FIGlet (.flf) font
+#[derive(Clone, Debug)]
+pub struct Font {
+    pub name: String,
+    pub height: usize,
+    /// Row the letters sit on, counted from the top
+    pub baseline: usize,
+    /// Stands in for a space that must not be smushed
+    hardblank: char,
+    /// How letters are pushed together, using the layout bits
+    layout: u32,
+    chars: hashbrown::HashMap<char, Vec<Vec<char>>>,
+}
+
+impl Font {
+    /// All of the fonts that ship with the binary
+    pub fn bundled() -> Vec<Self> {
+        BUNDLED
+            .iter()
+            .filter_map(|(name, source)| Self::parse(name, source).ok())
+            .collect()
+    }
+
+    pub fn parse(name: &str, source: &str) -> Result<Self, FontError> {
+        let mut lines = source.lines().map(|l| l.trim_end_matches('\r'));
+
+        let header = lines.next().ok_or(FontError::NotAFont)?;
+        let signature = header.strip_prefix("flf2a").ok_or(FontError::NotAFont)?;
+        let hardblank = signature.chars().next().ok_or(FontError::NotAFont)?;
+
+        let params: Vec<i64> = signature[hardblank.len_utf8()..]
+            .split_whitespace()
+            .map_while(|p| p.parse().ok())
+            .collect();
+        let &[height, baseline, _max_length, old_layout, comment_lines, ..] = params.as_slice()
+        else {
+            return Err(FontError::NotAFont);
+        };
+        if height < 1 {
+            return Err(FontError::NotAFont);
+        }
+
+        let layout = match params.get(6) {
+            Some(&full_layout) => full_layout as u32,
+            None if old_layout < 0 => 0,
+            None if old_layout == 0 => FITTING,
+            None => SMUSHING | (old_layout as u32 & 63),
+        };
+
+        let mut lines = lines.skip(comment_lines as usize);
+        let height = height as usize;
+        let mut read_char = || -> Option<Vec<Vec<char>>> {
+            (0..height)
+                .map(|_| lines.next().map(strip_endmark))
+                .collect()
+        };
+
+        let mut chars = hashbrown::HashMap::new();
+        for code in 32..127 {
+            let glyph = read_char().ok_or(FontError::Truncated)?;
+            chars.insert(char::from(code as u8), glyph);
+        }
+        // Older fonts leave these out
+        for code in DEUTSCH {
+            let Some(glyph) = read_char() else {
+                break;
+            };
+            chars.extend(char::from_u32(code).map(|c| (c, glyph)));
+        }
+
+        Ok(Self {
+            name: name.into(),
+            height,
+            baseline: (baseline.clamp(1, height as i64)) as usize,
+            hardblank,
+            layout,
+            chars,
+        })
+    }
+
+    /// Reads the fonts given by the user, the file name becomes the font name
+    pub fn open_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let source = std::fs::read_to_string(path)?;
+        let name = std::path::Path::new(path)
+            .file_stem()
+            .map_or(path.into(), |s| s.to_string_lossy());
+
+        Ok(Self::parse(&name, &source)?)
+    }
+
+    /// Renders one line of text, chars missing from the font are skipped
+    pub fn render(&self, text: &[char]) -> Vec<Vec<char>> {
+        let mut out = vec![vec![]; self.height];
+        let mut prev_width = 0;
+
+        for glyph in text.iter().filter_map(|c| self.chars.get(c)) {
+            let width = glyph.iter().map(Vec::len).max().unwrap_or(0);
+            let overlap = self.overlap(&out, glyph, prev_width, width);
+
+            for (row, line) in out.iter_mut().zip(glyph) {
+                let start = row.len() - overlap.min(row.len());
+
+                for i in 0..width {
+                    let c = line.get(i).copied().unwrap_or(' ');
+                    match row.get_mut(start + i) {
+                        Some(old) => *old = self.smush(*old, c, prev_width, width).unwrap_or(c),
+                        None => row.push(c),
+                    }
+                }
+            }
+            prev_width = width;
+        }
+
+        out.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&c| if c == self.hardblank { ' ' } else { c })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// How far the next glyph can slide into the rendered text
+    fn overlap(&self, out: &[Vec<char>], glyph: &[Vec<char>], prev: usize, width: usize) -> usize {
+        if self.layout & (FITTING | SMUSHING) == 0 {
+            return 0;
+        }
+
+        out.iter()
+            .zip(glyph)
+            .map(|(row, line)| {
+                let trailing = row.iter().rev().take_while(|&&c| c == ' ').count();
+                let leading = line.iter().take_while(|&&c| c == ' ').count();
+                let mut amount = trailing + leading;
+
+                let left = row.len().checked_sub(trailing + 1).map(|i| row[i]);
+                let right = line.get(leading).copied();
+                match (left, right) {
+                    (None, _) => amount += 1,
+                    (Some(l), Some(r)) if self.smush(l, r, prev, width).is_some() => amount += 1,
+                    _ => {}
+                }
+
+                amount.min(width)
+            })
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// The char two overlapping chars turn into, [None] if they can't overlap
+    fn smush(&self, left: char, right: char, prev: usize, width: usize) -> Option<char> {
+        if left == ' ' {
+            return Some(right);
+        }
+        if right == ' ' {
+            return Some(left);
+        }
+        if prev < 2 || width < 2 || self.layout & SMUSHING == 0 {
+            return None;
+        }
+
+        let hardblank = self.hardblank;
+        let rules = self.layout & 63;
+        if rules == 0 {
+            return Some(if right == hardblank { left } else { right });
+        }
+
+        if left == hardblank || right == hardblank {
+            return (rules & SMUSH_HARDBLANK != 0 && left == right).then_some(left);
+        }
+        if rules & SMUSH_EQUAL != 0 && left == right {
+            return Some(left);
+        }
+
+        const BRACKETS: &str = "|/\\[]{}()<>";
+        if rules & SMUSH_LOWLINE != 0 {
+            if left == '_' && BRACKETS.contains(right) {
+                return Some(right);
+            }
+            if right == '_' && BRACKETS.contains(left) {
+                return Some(left);
+            }
+        }
+
+        if rules & SMUSH_HIERARCHY != 0 {
+            let class = |c: char| {
+                ["|", "/\\", "[]", "{}", "()", "<>"]
+                    .iter()
+                    .position(|s| s.contains(c))
+            };
+            if let (Some(l), Some(r)) = (class(left), class(right)) {
+                if l != r {
+                    return Some(if l > r { left } else { right });
+                }
+            }
+        }
+
+        if rules & SMUSH_PAIR != 0
+            && matches!(
+                (left, right),
+                ('[', ']') | (']', '[') | ('{', '}') | ('}', '{') | ('(', ')') | (')', '(')
+            )
+        {
+            return Some('|');
+        }
+
+        if rules & SMUSH_BIGX != 0 {
+            match (left, right) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+/// Drops the endmark (the last char of the line, repeated on the last line of a glyph)
+fn strip_endmark(line: &str) -> Vec<char> {
+    let line = line.trim_end();
+    match line.chars().last() {
+        Some(mark) => line.trim_end_matches(mark).chars().collect(),
+        None => vec![],
+    }
+}
@@ -8,6 +8,16 @@ pub enum HistoryAction {
     LayerUp(u32),
     LayerDown(u32),
     Draw(u32, LayerData),
+    /// Every cell of the layer moved by (dx, dy), with the cells that got pushed off the canvas
+    LayerMoved(u32, (i32, i32), LayerData),
+    /// The copy and its index
+    LayerDuplicated(Layer, usize),
+    /// The layers that were merged, the layer they were merged into and its index
+    LayersMerged(Vec<Layer>, Layer, usize),
+    /// The layers that were flattened and the layer they were flattened into
+    LayersFlattened(Vec<Layer>, Layer),
+    /// Several actions that are undone and redone together
+    Group(Vec<HistoryAction>),
 }
 
 #[derive(Debug, Default)]
@@ -36,6 +46,24 @@ impl History {
         self.past.push(HistoryAction::LayerRenamed(id, old_name));
     }
 
+    pub fn move_layer(&mut self, id: u32, offset: (i32, i32), clipped: LayerData) {
+        self.past
+            .push(HistoryAction::LayerMoved(id, offset, clipped));
+    }
+
+    pub fn duplicate_layer(&mut self, copy: Layer, index: usize) {
+        self.past.push(HistoryAction::LayerDuplicated(copy, index));
+    }
+
+    pub fn merge_layers(&mut self, layers: Vec<Layer>, merged: Layer, index: usize) {
+        self.past
+            .push(HistoryAction::LayersMerged(layers, merged, index));
+    }
+
+    pub fn flatten_layers(&mut self, layers: Vec<Layer>, flat: Layer) {
+        self.past.push(HistoryAction::LayersFlattened(layers, flat));
+    }
+
     pub fn layer_up(&mut self, id: u32) {
         self.past.push(HistoryAction::LayerUp(id));
     }
@@ -44,6 +72,15 @@ impl History {
         self.past.push(HistoryAction::LayerDown(id));
     }
 
+    /// Records actions as one step
+    pub fn group(&mut self, actions: Vec<HistoryAction>) {
+        match actions.len() {
+            0 => {}
+            1 => self.past.extend(actions),
+            _ => self.past.push(HistoryAction::Group(actions)),
+        }
+    }
+
     pub fn forget_redo(&mut self) {
         self.future.clear();
     }
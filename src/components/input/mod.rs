@@ -16,6 +16,11 @@ pub enum InputMode {
     Help,
     Export,
     Save,
+    /// Typing onto the canvas with the text tool
+    Text,
+    /// Typing in the ramp of the shade tool
+    Ramp,
+    Replace,
     Exit,
     TooSmall,
     #[cfg(debug_assertions)]
@@ -50,7 +55,9 @@ impl InputCapture {
 
     pub fn get(&self, x: u16, y: u16) -> Option<&ClickAction> {
         match self.mode {
-            InputMode::Normal | InputMode::Help => &self.normal_input,
+            InputMode::Normal | InputMode::Help | InputMode::Text | InputMode::Ramp => {
+                &self.normal_input
+            }
             _ => &self.popup_layer,
         }
         .get(&(x, y))
@@ -93,7 +100,10 @@ impl InputCapture {
             return;
         }
 
-        if !matches!(new_mode, InputMode::Normal | InputMode::Help) {
+        if !matches!(
+            new_mode,
+            InputMode::Normal | InputMode::Help | InputMode::Text | InputMode::Ramp
+        ) {
             self.text_area.clear();
             self.color_picker.reset();
             self.popup_layer.clear();
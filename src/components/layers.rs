@@ -1,17 +1,181 @@
+use std::fmt;
+
+use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 
 use super::cell::Cell;
+use super::palette;
 
 /// Wrapper type for the HashMap that stores the layer
 /// Indexing begins at (1, 1), values below will be ignored
 pub type LayerData = hashbrown::HashMap<(u16, u16), Cell>;
 
+/// Moves every cell by (dx, dy). Returns the moved cells and, at their old positions,
+/// the cells that would end up past the edges, `max` being the last position inside them.
+pub fn translate(data: &LayerData, dx: i32, dy: i32, max: (u16, u16)) -> (LayerData, LayerData) {
+    let mut moved = LayerData::new();
+    let mut clipped = LayerData::new();
+
+    for (&(x, y), &cell) in data {
+        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+        if nx < 1 || ny < 1 || nx > max.0 as i32 || ny > max.1 as i32 {
+            clipped.insert((x, y), cell);
+        } else {
+            moved.insert((nx as u16, ny as u16), cell);
+        }
+    }
+
+    (moved, clipped)
+}
+
+/// A layer being dragged around with the move tool
+#[derive(Clone, Copy, Debug)]
+pub struct LayerDrag {
+    pub layer_id: u32,
+    /// Cell the drag started on
+    pub start: (u16, u16),
+    /// How far the layer has been dragged so far
+    pub offset: (i32, i32),
+}
+
+/// Cells lifted off a layer to be moved around, they are drawn in place until they are put back
+#[derive(Clone, Debug)]
+pub struct Floating {
+    pub layer_id: u32,
+    /// The lifted cells where they are now
+    pub cells: LayerData,
+    /// The cells as they were on the layer before they were lifted
+    pub lifted: LayerData,
+}
+
+/// How the cells of a layer are put over the layers below it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Blend {
+    /// Cells replace the ones below
+    #[default]
+    Normal,
+    /// Only the chars are taken, the colors below are kept
+    Glyph,
+    /// Only the fg color is taken
+    Fg,
+    /// Only the bg color is taken
+    Bg,
+    /// Like normal, but an unset bg lets the bg below show through
+    TransparentBg,
+}
+
+impl fmt::Display for Blend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "Norm"),
+            Self::Glyph => write!(f, "Glyph"),
+            Self::Fg => write!(f, "FG"),
+            Self::Bg => write!(f, "BG"),
+            Self::TransparentBg => write!(f, "NoBG"),
+        }
+    }
+}
+
+impl Blend {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Normal => Self::Glyph,
+            Self::Glyph => Self::Fg,
+            Self::Fg => Self::Bg,
+            Self::Bg => Self::TransparentBg,
+            Self::TransparentBg => Self::Normal,
+        }
+    }
+
+    /// The cell showing when `cell` is put over `below`
+    pub fn apply(&self, below: Cell, cell: Cell) -> Cell {
+        match self {
+            Self::Normal => cell,
+            Self::Glyph => Cell {
+                char: cell.char,
+                ..below
+            },
+            Self::Fg => Cell {
+                fg: cell.fg,
+                ..below
+            },
+            Self::Bg => Cell {
+                bg: cell.bg,
+                ..below
+            },
+            Self::TransparentBg if cell.bg == Color::Reset => Cell {
+                bg: below.bg,
+                ..cell
+            },
+            Self::TransparentBg => cell,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Layer {
     pub name: String,
     pub visible: bool,
     pub id: u32,
     pub data: LayerData,
+    /// How the layer is put over the layers below it
+    #[serde(default)]
+    pub blend: Blend,
+    /// How much of the colors below are covered, in percent
+    #[serde(default = "opaque")]
+    pub opacity: u8,
+    /// Nothing can be drawn on or erased from the layer
+    #[serde(default)]
+    pub locked: bool,
+    /// Only the colors of cells that are already there can be changed
+    #[serde(default)]
+    pub alpha_locked: bool,
+}
+
+const fn opaque() -> u8 {
+    100
+}
+
+/// Text color unset colors below a faded layer are taken to be, that of a dark terminal
+pub const DEFAULT_FG: (u8, u8, u8) = (229, 229, 229);
+/// Background unset colors below a faded layer are taken to be, that of a dark terminal
+pub const DEFAULT_BG: (u8, u8, u8) = (0, 0, 0);
+
+/// Mixes the colors of `cell` with the ones of `below`, unset colors of `cell` stay
+/// see-through and unset colors below are mixed as [DEFAULT_FG] and [DEFAULT_BG].
+/// Under half opacity the glyph below shows instead of the one of `cell`.
+fn fade(below: Cell, cell: Cell, opacity: u8) -> Cell {
+    match opacity {
+        100.. => return cell,
+        0 => return below,
+        _ => {}
+    }
+
+    let t = opacity as f64 / 100.0;
+    let mix = |below: Color, color: Color, default: (u8, u8, u8)| {
+        if color == Color::Reset {
+            return below;
+        }
+        if color == below {
+            return color;
+        }
+        let from = match below {
+            Color::Reset => default,
+            below => palette::rgb(below),
+        };
+        let (r, g, b) = palette::lerp(from, palette::rgb(color), t);
+        Color::Rgb(r, g, b)
+    };
+
+    Cell {
+        fg: mix(below.fg, cell.fg, DEFAULT_FG),
+        bg: mix(below.bg, cell.bg, DEFAULT_BG),
+        char: if opacity < 50 && below.char != ' ' {
+            below.char
+        } else {
+            cell.char
+        },
+    }
 }
 
 impl Layer {
@@ -21,6 +185,10 @@ impl Layer {
             visible: true,
             id: alea::u32(),
             data: LayerData::default(),
+            blend: Blend::default(),
+            opacity: opaque(),
+            locked: false,
+            alpha_locked: false,
         };
         let id = this.id;
         (this, id)
@@ -29,14 +197,106 @@ impl Layer {
     pub fn toggle_visible(&mut self) {
         self.visible = !self.visible;
     }
+
+    /// Whether either lock keeps the layer from being rewritten as a whole
+    pub const fn is_locked(&self) -> bool {
+        self.locked || self.alpha_locked
+    }
+
+    /// Label telling which lock is on, [None] when the layer isn't locked
+    pub const fn lock_label(&self) -> Option<&'static str> {
+        match (self.locked, self.alpha_locked) {
+            (true, _) => Some(" Layer locked "),
+            (_, true) => Some(" Alpha locked "),
+            _ => None,
+        }
+    }
+
+    /// Puts the cells of the layer over `page` with its blend mode and opacity
+    pub fn composite(&self, page: &mut LayerData) {
+        for (&pos, &cell) in &self.data {
+            if cell == Cell::default() {
+                continue;
+            }
+            let below = page.get(&pos).copied().unwrap_or_default();
+            let cell = fade(below, self.blend.apply(below, cell), self.opacity);
+            if cell != Cell::default() {
+                page.insert(pos, cell);
+            }
+        }
+    }
+
+    /// Writes `cell` as far as the locks allow it, the empty cell erases.
+    /// Returns the cell that was there before.
+    pub fn put(&mut self, pos: (u16, u16), cell: Cell) -> Cell {
+        let old_cell = self.data.get(&pos).copied().unwrap_or_default();
+
+        let cell = match (self.locked, self.alpha_locked) {
+            (true, _) => return old_cell,
+            (_, true) if old_cell == Cell::default() || cell == Cell::default() => return old_cell,
+            // Alpha locked cells keep their glyph and only take on the new colors
+            (_, true) => Cell {
+                char: old_cell.char,
+                ..cell
+            },
+            _ => cell,
+        };
+
+        if cell == Cell::default() {
+            self.data.remove(&pos);
+        } else {
+            self.data.insert(pos, cell);
+        }
+
+        old_cell
+    }
+}
+
+/// Whether `upper` composited onto `lower` looks the same as the two layers on their own.
+/// The merged layer is shown like `lower`, so that has to cover what is below it as it is,
+/// and cells of `upper` over nothing of `lower` have to do so too.
+fn can_merge(lower: &Layer, upper: &Layer) -> bool {
+    let plain = |layer: &Layer| layer.blend == Blend::Normal && layer.opacity >= 100;
+    let covered = || upper.data.keys().all(|pos| lower.data.contains_key(pos));
+
+    !upper.visible || (lower.visible && plain(lower) && (plain(upper) || covered()))
+}
+
+/// The layer as it is shown while it is dragged or some of its cells are floating,
+/// [None] when it is shown as it is
+fn shown(layer: &Layer, floating: Option<&Floating>, dragged: Option<&LayerDrag>) -> Option<Layer> {
+    if let Some(drag) = dragged.filter(|d| d.layer_id == layer.id) {
+        let (dx, dy) = drag.offset;
+        let (data, _) = translate(&layer.data, dx, dy, (u16::MAX, u16::MAX));
+        return Some(Layer {
+            data,
+            ..layer.clone()
+        });
+    }
+
+    let floating = floating.filter(|f| f.layer_id == layer.id)?;
+
+    let mut shown = layer.clone();
+    shown
+        .data
+        .extend(floating.cells.iter().map(|(&pos, &cell)| (pos, cell)));
+    Some(shown)
 }
 
 #[derive(Debug, Clone)]
 pub struct Layers {
     pub layers: Vec<Layer>,
     pub last_pos: Option<(u16, u16)>,
+    /// Starting point of the shape being dragged out
+    pub anchor: Option<(u16, u16)>,
+    /// Cells of the shape being dragged out, drawn over the canvas but not part of any layer
+    pub preview: LayerData,
     pub active: usize,
     pub id_list: Vec<u32>,
+    /// Cells of the selection that are being moved around
+    pub floating: Option<Floating>,
+    /// Layer being dragged with the move tool, it is shown moved until it is let go
+    pub dragged: Option<LayerDrag>,
     rendered: Option<LayerData>,
 }
 
@@ -47,7 +307,11 @@ impl Default for Layers {
             layers: vec![layer],
             active: 0,
             last_pos: None,
+            anchor: None,
+            preview: LayerData::default(),
             id_list: vec![id],
+            floating: None,
+            dragged: None,
             rendered: None,
         }
     }
@@ -82,6 +346,39 @@ impl Layers {
         }
     }
 
+    pub fn cycle_blend(&mut self, index: u8) {
+        self.queue_render();
+        if let Some(layer) = self.layers.get_mut(index as usize) {
+            layer.blend = layer.blend.next();
+        }
+    }
+
+    /// Locks or unlocks a layer for drawing
+    pub fn toggle_locked(&mut self, index: u8) {
+        if let Some(layer) = self.layers.get_mut(index as usize) {
+            layer.locked = !layer.locked;
+        }
+    }
+
+    /// Locks or unlocks the empty cells of a layer, only the drawn ones can still be recolored
+    pub fn toggle_alpha_locked(&mut self, index: u8) {
+        if let Some(layer) = self.layers.get_mut(index as usize) {
+            layer.alpha_locked = !layer.alpha_locked;
+        }
+    }
+
+    /// Raises or lowers the opacity of a layer by a tenth
+    pub fn step_opacity(&mut self, index: u8, up: bool) {
+        self.queue_render();
+        if let Some(layer) = self.layers.get_mut(index as usize) {
+            layer.opacity = if up {
+                (layer.opacity + 10).min(100)
+            } else {
+                layer.opacity.saturating_sub(10)
+            };
+        }
+    }
+
     pub fn add_layer(&mut self) -> u32 {
         let (new_layer, new_layer_id) = Layer::new();
 
@@ -184,13 +481,8 @@ impl Layers {
         false
     }
 
-    pub fn get_display_info(&self) -> Vec<(usize, (&str, bool))> {
-        self.layers
-            .iter()
-            .map(|l| (l.name.as_str(), l.visible))
-            .rev()
-            .enumerate()
-            .collect()
+    pub fn get_display_info(&self) -> Vec<(usize, &Layer)> {
+        self.layers.iter().rev().enumerate().collect()
     }
 
     pub fn queue_render(&mut self) {
@@ -199,6 +491,9 @@ impl Layers {
 
     /// Combine all of the layers into a final output
     pub fn render(&mut self) -> LayerData {
+        let floating = self.floating.as_ref();
+        let dragged = self.dragged.as_ref();
+
         self.rendered
             .get_or_insert_with(|| {
                 self.layers
@@ -206,13 +501,108 @@ impl Layers {
                     // Only render visible layers
                     .filter(|l| l.visible)
                     .fold(LayerData::default(), |mut page, layer| {
-                        page.extend(layer.data.iter().filter(|&(_, &c)| c != Cell::default()));
+                        match shown(layer, floating, dragged) {
+                            Some(shown) => shown.composite(&mut page),
+                            None => layer.composite(&mut page),
+                        }
                         page
                     })
             })
             .clone()
     }
 
+    /// Moves all of the cells of a layer, returns the ones that were pushed past `max`
+    /// or the top left corner
+    pub fn move_layer(&mut self, layer_id: u32, dx: i32, dy: i32, max: (u16, u16)) -> LayerData {
+        let layer = self.get_layer_mut(layer_id);
+        let (moved, clipped) = translate(&layer.data, dx, dy, max);
+        layer.data = moved;
+        clipped
+    }
+
+    /// Puts a copy of the active layer above it, returns the copy and where it went
+    pub fn duplicate_active_layer(&mut self) -> (Layer, usize) {
+        self.check_self();
+        let mut copy = self.layers[self.active].clone();
+        copy.id = self.fresh_id();
+        copy.name = format!("{} copy", copy.name);
+
+        let index = self.active + 1;
+        self.insert_layer(copy.clone(), index);
+        self.active = index;
+        self.queue_render();
+
+        (copy, index)
+    }
+
+    /// Composites the active layer onto the one below it. Returns the two layers as they were,
+    /// the merged layer that took their place and its index, or [None] for the bottom layer,
+    /// when either layer is locked and when one layer can't look like the two of them.
+    pub fn merge_down(&mut self) -> Option<(Vec<Layer>, Layer, usize)> {
+        self.check_self();
+        if self.active == 0 {
+            return None;
+        }
+        let [lower, upper] = &self.layers[self.active - 1..=self.active] else {
+            return None;
+        };
+        if lower.is_locked() || upper.is_locked() || !can_merge(lower, upper) {
+            return None;
+        }
+
+        let index = self.active - 1;
+        let merged_away: Vec<Layer> = self.layers.drain(index..=self.active).collect();
+        let [lower, upper] = merged_away.as_slice() else {
+            return None;
+        };
+
+        let mut merged = lower.clone();
+        if upper.visible {
+            upper.composite(&mut merged.data);
+        }
+        self.id_list.retain(|&id| id != upper.id);
+
+        self.layers.insert(index, merged.clone());
+        self.active = index;
+        self.queue_render();
+
+        Some((merged_away, merged, index))
+    }
+
+    /// Replaces every layer with a single one holding what is visible,
+    /// returns the old layers and the new one or [None] when any layer is locked
+    pub fn flatten(&mut self) -> Option<(Vec<Layer>, Layer)> {
+        if self.layers.iter().any(Layer::is_locked) {
+            return None;
+        }
+
+        let (mut flat, _) = Layer::new();
+        flat.id = self.fresh_id();
+        flat.name = "Flattened".into();
+        flat.data = self.render();
+
+        let old_layers = self.replace_all(vec![flat.clone()]);
+        Some((old_layers, flat))
+    }
+
+    /// Swaps out the whole layer stack, returns the old layers
+    pub fn replace_all(&mut self, layers: Vec<Layer>) -> Vec<Layer> {
+        self.id_list = layers.iter().map(|l| l.id).collect();
+        self.active = 0;
+        self.queue_render();
+        std::mem::replace(&mut self.layers, layers)
+    }
+
+    /// An id that no layer has yet
+    fn fresh_id(&self) -> u32 {
+        loop {
+            let id = alea::u32();
+            if !self.id_list.contains(&id) {
+                return id;
+            }
+        }
+    }
+
     pub fn remove_layer_by_id(&mut self, id: u32) {
         self.layers.retain(|l| l.id != id);
         self.id_list.retain(|id0| id0 != &id);
@@ -1,10 +1,19 @@
+pub mod box_drawing;
 pub mod brush;
 pub mod cell;
 pub mod charpicker;
 pub mod clicks;
+pub mod figlet;
 pub mod history;
 pub mod input;
 pub mod layers;
 pub mod palette;
+pub mod pixels;
+pub mod replace;
 pub mod save_load;
+pub mod selection;
+pub mod shapes;
+pub mod symmetry;
+pub mod text_entry;
 pub mod tools;
+pub mod transform;
@@ -1,6 +1,118 @@
+use std::fmt;
+
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 
+/// The 16 named colors in the order of their ANSI codes, with the usual xterm values
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (128, 0, 0)),
+    (Color::Green, (0, 128, 0)),
+    (Color::Yellow, (128, 128, 0)),
+    (Color::Blue, (0, 0, 128)),
+    (Color::Magenta, (128, 0, 128)),
+    (Color::Cyan, (0, 128, 128)),
+    (Color::Gray, (192, 192, 192)),
+    (Color::DarkGray, (128, 128, 128)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (0, 0, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of each channel in the 6×6×6 color cube of the 256 colors
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Palette that computed colors get rounded to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quantize {
+    /// Keep the exact color
+    #[default]
+    Rgb,
+    /// The 16 named colors
+    Ansi16,
+    /// The 256 indexed colors
+    Ansi256,
+}
+
+impl fmt::Display for Quantize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Rgb => write!(f, "RGB"),
+            Self::Ansi16 => write!(f, "16 Colors"),
+            Self::Ansi256 => write!(f, "256 Colors"),
+        }
+    }
+}
+
+impl Quantize {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Rgb => Self::Ansi16,
+            Self::Ansi16 => Self::Ansi256,
+            Self::Ansi256 => Self::Rgb,
+        }
+    }
+
+    /// The closest color of the palette
+    pub fn color(&self, (r, g, b): (u8, u8, u8)) -> Color {
+        match self {
+            Self::Rgb => Color::Rgb(r, g, b),
+            Self::Ansi16 => nearest(ANSI.iter().copied(), (r, g, b)),
+            // The first 16 depend on the terminal theme, so only the cube and grays are used
+            Self::Ansi256 => nearest(
+                (16..=255).map(|i| (Color::Indexed(i), rgb(Color::Indexed(i)))),
+                (r, g, b),
+            ),
+        }
+    }
+}
+
+/// The RGB value of any color, [Color::Reset] counts as black
+pub fn rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i @ 0..=15) => ANSI[i as usize].1,
+        Color::Indexed(i @ 16..=231) => {
+            let i = i as usize - 16;
+            (CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6])
+        }
+        Color::Indexed(i) => {
+            let gray = 8 + 10 * (i - 232);
+            (gray, gray, gray)
+        }
+        Color::Reset => (0, 0, 0),
+        named => ANSI
+            .iter()
+            .find(|&&(c, _)| c == named)
+            .map_or((0, 0, 0), |&(_, rgb)| rgb),
+    }
+}
+
+/// The color `t` of the way from `from` to `to`
+pub fn lerp(from: (u8, u8, u8), to: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    (
+        channel(from.0, to.0),
+        channel(from.1, to.1),
+        channel(from.2, to.2),
+    )
+}
+
+fn nearest(colors: impl Iterator<Item = (Color, (u8, u8, u8))>, (r, g, b): (u8, u8, u8)) -> Color {
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        d(cr, r) + d(cg, g) + d(cb, b)
+    };
+
+    colors
+        .min_by_key(|&(_, rgb)| distance(rgb))
+        .map_or(Color::Reset, |(color, _)| color)
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Palette {
     pub colors: Vec<Color>,
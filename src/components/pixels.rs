@@ -0,0 +1,236 @@
+use std::fmt;
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use super::brush::Brush;
+use super::cell::Cell;
+
+/// Splits every cell into smaller pixels that get drawn on instead of whole cells
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PixelMode {
+    #[default]
+    Off,
+    /// Two pixels stacked on top of each other, each with its own color
+    HalfBlock,
+    /// Two columns of four braille dots, all in the same color
+    Braille,
+    /// 2×2 quadrant blocks, the fg color is on and the bg color off
+    Quadrant,
+    /// 2×3 sextant blocks (Unicode 13), the fg color is on and the bg color off
+    Sextant,
+}
+
+impl fmt::Display for PixelMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "Off"),
+            Self::HalfBlock => write!(f, "Half Block"),
+            Self::Braille => write!(f, "Braille"),
+            Self::Quadrant => write!(f, "Quadrant"),
+            Self::Sextant => write!(f, "Sextant"),
+        }
+    }
+}
+
+/// Which pixel of a cell the mouse lands on, terminals only report whole cells
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Corner {
+    /// The pixel picked for the left button
+    #[default]
+    Picked,
+    /// The pixel diagonally across the cell from the picked one
+    Opposite,
+}
+
+impl PixelMode {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Off => Self::HalfBlock,
+            Self::HalfBlock => Self::Braille,
+            Self::Braille => Self::Quadrant,
+            Self::Quadrant => Self::Sextant,
+            Self::Sextant => Self::Off,
+        }
+    }
+
+    /// Pixels per cell as (columns, rows)
+    pub const fn size(&self) -> (u16, u16) {
+        match self {
+            Self::Off => (1, 1),
+            Self::HalfBlock => (1, 2),
+            Self::Braille => (2, 4),
+            Self::Quadrant => (2, 2),
+            Self::Sextant => (2, 3),
+        }
+    }
+
+    /// Position of a pixel inside the cell it belongs to, `picked` wraps around when it
+    /// was picked for a larger mode
+    pub const fn corner(&self, corner: Corner, picked: (u16, u16)) -> (u16, u16) {
+        let (width, height) = self.size();
+        let (x, y) = (picked.0 % width, picked.1 % height);
+        match corner {
+            Corner::Picked => (x, y),
+            Corner::Opposite => (width - 1 - x, height - 1 - y),
+        }
+    }
+
+    /// The pixel after `picked`, going along the rows of the cell
+    pub const fn next_pixel(&self, picked: (u16, u16)) -> (u16, u16) {
+        let (width, height) = self.size();
+        let (x, y) = (picked.0 % width, picked.1 % height);
+        if x + 1 < width {
+            (x + 1, y)
+        } else {
+            (0, (y + 1) % height)
+        }
+    }
+
+    /// Turns the pixel at `sub` of `cell` on or off, [None] when the whole cell ends up empty
+    pub fn paint(
+        &self,
+        cell: Option<Cell>,
+        sub: (u16, u16),
+        on: bool,
+        brush: &Brush,
+    ) -> Option<Cell> {
+        match self {
+            Self::Off => cell,
+            Self::HalfBlock => {
+                let [mut top, mut bottom] = half_blocks(cell);
+                let color = if on { brush.fg } else { Color::Reset };
+                if sub.1 == 0 {
+                    top = color;
+                } else {
+                    bottom = color;
+                }
+                half_block_cell(top, bottom)
+            }
+            Self::Braille | Self::Quadrant | Self::Sextant => {
+                let old = cell.and_then(|c| self.dots(c.char)).unwrap_or(0);
+                let dots = if on {
+                    old | self.dot(sub)
+                } else {
+                    old & !self.dot(sub)
+                };
+                if dots == 0 {
+                    return None;
+                }
+
+                let char = self.glyph(dots)?;
+                match cell {
+                    // Turning dots off keeps the colors the other dots were drawn with
+                    Some(cell) if !on => Some(Cell { char, ..cell }),
+                    _ => Some(Cell {
+                        char,
+                        ..brush.as_cell()
+                    }),
+                }
+            }
+        }
+    }
+}
+
+/// The empty braille pattern, the dots are the bits added onto it
+const BRAILLE: u32 = 0x2800;
+
+/// Quadrant blocks indexed by their dots
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// The first sextant, they are counted up by their dots leaving out the ones that
+/// already exist as half blocks
+const SEXTANT: u32 = 0x1FB00;
+
+/// Sextant dots that look like the left half, the right half and the full block
+const SEXTANT_LEFT: u8 = 0b010101;
+const SEXTANT_RIGHT: u8 = 0b101010;
+const SEXTANT_FULL: u8 = 0b111111;
+
+impl PixelMode {
+    /// Bit of the dot at `sub` in the dots of a glyph
+    const fn dot(&self, (x, y): (u16, u16)) -> u8 {
+        match (self, x, y) {
+            // The bottom row of braille was added to the encoding last
+            (Self::Braille, 0, 3) => 0x40,
+            (Self::Braille, _, 3) => 0x80,
+            (Self::Braille, 0, y) => 1 << y,
+            (Self::Braille, _, y) => 1 << (y + 3),
+            (_, x, y) => 1 << (y * 2 + x),
+        }
+    }
+
+    /// The dots that are on in `c`, [None] when `c` is not one of the glyphs of this mode
+    fn dots(&self, c: char) -> Option<u8> {
+        match self {
+            Self::Off | Self::HalfBlock => None,
+            Self::Braille => (c as u32)
+                .checked_sub(BRAILLE)
+                .and_then(|dots| u8::try_from(dots).ok()),
+            Self::Quadrant => QUADRANTS.iter().position(|&q| q == c).map(|i| i as u8),
+            Self::Sextant => match c {
+                ' ' => Some(0),
+                '▌' => Some(SEXTANT_LEFT),
+                '▐' => Some(SEXTANT_RIGHT),
+                '█' => Some(SEXTANT_FULL),
+                _ => {
+                    let index = (c as u32).checked_sub(SEXTANT).filter(|&i| i < 60)?;
+                    let mut dots = index as u8 + 1;
+                    if dots >= SEXTANT_LEFT {
+                        dots += 1;
+                    }
+                    if dots >= SEXTANT_RIGHT {
+                        dots += 1;
+                    }
+                    Some(dots)
+                }
+            },
+        }
+    }
+
+    /// The glyph showing `dots`
+    fn glyph(&self, dots: u8) -> Option<char> {
+        match self {
+            Self::Off | Self::HalfBlock => None,
+            Self::Braille => char::from_u32(BRAILLE + dots as u32),
+            Self::Quadrant => QUADRANTS.get(dots as usize).copied(),
+            Self::Sextant => match dots {
+                0 => Some(' '),
+                SEXTANT_LEFT => Some('▌'),
+                SEXTANT_RIGHT => Some('▐'),
+                SEXTANT_FULL => Some('█'),
+                _ => {
+                    let skipped = (dots > SEXTANT_LEFT) as u32 + (dots > SEXTANT_RIGHT) as u32;
+                    char::from_u32(SEXTANT + dots as u32 - 1 - skipped)
+                }
+            },
+        }
+    }
+}
+
+/// Colors of the upper and lower half of a cell
+fn half_blocks(cell: Option<Cell>) -> [Color; 2] {
+    let Some(cell) = cell else {
+        return [Color::Reset; 2];
+    };
+
+    match cell.char {
+        '▀' => [cell.fg, cell.bg],
+        '▄' => [cell.bg, cell.fg],
+        '█' => [cell.fg, cell.fg],
+        _ => [cell.bg, cell.bg],
+    }
+}
+
+fn half_block_cell(top: Color, bottom: Color) -> Option<Cell> {
+    let (char, fg, bg) = match (top, bottom) {
+        (Color::Reset, Color::Reset) => return None,
+        (top, bottom) if top == bottom => ('█', top, Color::Reset),
+        (Color::Reset, bottom) => ('▄', bottom, Color::Reset),
+        (top, bottom) => ('▀', top, bottom),
+    };
+
+    Some(Cell { fg, bg, char })
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+use super::cell::Cell;
+use super::tools::FillMatch;
+
+/// Cells the replace popup looks through
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReplaceScope {
+    /// The active layer
+    #[default]
+    Layer,
+    /// Every visible layer
+    Visible,
+    /// The selected part of the active layer
+    Selection,
+}
+
+impl fmt::Display for ReplaceScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl ReplaceScope {
+    pub const fn all() -> [Self; 3] {
+        [Self::Layer, Self::Visible, Self::Selection]
+    }
+}
+
+/// Settings of the replace popup, they are kept while the popup is closed
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Replace {
+    /// Cell to look for, only the part chosen by `by` is compared
+    pub find: Cell,
+    /// Cell the matching part is taken from
+    pub with: Cell,
+    pub by: FillMatch,
+    pub scope: ReplaceScope,
+    /// Replacing in the selection was tried without one, shown in the popup until it closes
+    pub no_selection: bool,
+}
+
+impl Replace {
+    /// The cell with its matching part replaced, [None] when it doesn't match
+    pub fn apply(&self, cell: Cell) -> Option<Cell> {
+        if cell == Cell::default() || !self.by.matches(cell, self.find) {
+            return None;
+        }
+
+        let new_cell = match self.by {
+            FillMatch::Char => Cell {
+                char: self.with.char,
+                ..cell
+            },
+            FillMatch::Fg => Cell {
+                fg: self.with.fg,
+                ..cell
+            },
+            FillMatch::Bg => Cell {
+                bg: self.with.bg,
+                ..cell
+            },
+            FillMatch::Cell => self.with,
+        };
+
+        (new_cell != cell).then_some(new_cell)
+    }
+
+    pub fn swap(&mut self) {
+        std::mem::swap(&mut self.find, &mut self.with);
+    }
+}
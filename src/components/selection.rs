@@ -0,0 +1,83 @@
+use super::cell::Cell;
+use super::layers::LayerData;
+
+/// Edges of a rectangle as (left, top, right, bottom), the edges are part of it
+pub type Area = (u16, u16, u16, u16);
+
+#[derive(Debug, Default)]
+pub struct Selection {
+    /// Selected part of the active layer
+    pub area: Option<Area>,
+    /// Corner the marquee is being dragged out from
+    pub anchor: Option<(u16, u16)>,
+    /// Last cursor position while the selected cells are being dragged around
+    pub grab: Option<(u16, u16)>,
+    /// Copied cells, relative to the top left corner of the area they were copied from
+    pub clipboard: LayerData,
+    /// Width and height of the area the clipboard was copied from
+    pub clipboard_size: (u16, u16),
+}
+
+impl Selection {
+    pub fn contains(&self, pos: (u16, u16)) -> bool {
+        self.area.is_some_and(|area| contains(area, pos))
+    }
+
+    pub fn clear(&mut self) {
+        self.area = None;
+        self.anchor = None;
+        self.grab = None;
+    }
+
+    /// The cells of `layer` inside the selection
+    pub fn cells(&self, layer: &LayerData) -> LayerData {
+        self.area.map(|area| cells(area, layer)).unwrap_or_default()
+    }
+}
+
+pub fn contains((left, top, right, bottom): Area, (x, y): (u16, u16)) -> bool {
+    (left..=right).contains(&x) && (top..=bottom).contains(&y)
+}
+
+/// Every position inside the area, row by row
+pub fn positions((left, top, right, bottom): Area) -> Vec<(u16, u16)> {
+    (top..=bottom)
+        .flat_map(|y| (left..=right).map(move |x| (x, y)))
+        .collect()
+}
+
+/// The visible cells of `layer` inside the area
+pub fn cells(area: Area, layer: &LayerData) -> LayerData {
+    layer
+        .iter()
+        .filter(|(&pos, &cell)| contains(area, pos) && cell != Cell::default())
+        .map(|(&pos, &cell)| (pos, cell))
+        .collect()
+}
+
+/// Smallest area holding every visible cell of `layer`, [None] if there are none
+pub fn bounding_area(layer: &LayerData) -> Option<Area> {
+    layer
+        .iter()
+        .filter(|(_, &cell)| cell != Cell::default())
+        .fold(None, |area, (&(x, y), _)| {
+            Some(match area {
+                None => (x, y, x, y),
+                Some((left, top, right, bottom)) => {
+                    (left.min(x), top.min(y), right.max(x), bottom.max(y))
+                }
+            })
+        })
+}
+
+/// Moves the area by (dx, dy), [None] if that would push it past the top or left edge
+pub fn translate((left, top, right, bottom): Area, dx: i32, dy: i32) -> Option<Area> {
+    let shift = |v: u16, d: i32| u16::try_from(v as i32 + d).ok();
+
+    Some((
+        shift(left, dx)?,
+        shift(top, dy)?,
+        shift(right, dx)?,
+        shift(bottom, dy)?,
+    ))
+}
@@ -0,0 +1,225 @@
+use crate::app::connect_points;
+
+/// Every cell on a straight line from `start` to `end`, both ends included
+pub fn line(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    let mut out = vec![start];
+    out.extend(connect_points(start, Some(end)));
+    out.dedup();
+    out
+}
+
+/// Straight lines joining up all of the `points`, in order
+pub fn polyline(points: &[(u16, u16)]) -> Vec<(u16, u16)> {
+    let Some(&first) = points.first() else {
+        return vec![];
+    };
+
+    let mut out = vec![first];
+    for pair in points.windows(2) {
+        out.extend(connect_points(pair[0], Some(pair[1])));
+    }
+    out.dedup();
+    out
+}
+
+/// Orthogonal route from `start` to `end` with a bend halfway, it leaves and arrives
+/// along the direction they are further apart in
+pub fn elbow(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    let (dx, dy) = (start.0.abs_diff(end.0), start.1.abs_diff(end.1));
+
+    let bends = if dx >= dy {
+        let mid = (start.0 + end.0) / 2;
+        [(mid, start.1), (mid, end.1)]
+    } else {
+        let mid = (start.1 + end.1) / 2;
+        [(start.0, mid), (end.0, mid)]
+    };
+
+    polyline(&[start, bends[0], bends[1], end])
+}
+
+/// Bézier curve from the first to the last of `points`, the ones between are its control points
+pub fn bezier(points: &[(u16, u16)]) -> Vec<(u16, u16)> {
+    // Enough samples that the gaps between them are short, they get joined up with lines
+    let hull: u32 = points
+        .windows(2)
+        .map(|pair| {
+            pair[0]
+                .0
+                .abs_diff(pair[1].0)
+                .max(pair[0].1.abs_diff(pair[1].1)) as u32
+        })
+        .sum();
+    let steps = hull.max(1);
+
+    let samples: Vec<(u16, u16)> = (0..=steps)
+        .filter_map(|i| de_casteljau(points, i as f64 / steps as f64))
+        .collect();
+
+    polyline(&samples)
+}
+
+/// Point of the Bézier curve at `t`, found by repeatedly splitting the control polygon
+fn de_casteljau(points: &[(u16, u16)], t: f64) -> Option<(u16, u16)> {
+    let mut points: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (x as f64, y as f64)).collect();
+
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|pair| {
+                let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t)
+            })
+            .collect();
+    }
+
+    points
+        .first()
+        .map(|&(x, y)| (x.round() as u16, y.round() as u16))
+}
+
+/// Outline of the rectangle with opposite corners `start` and `end`
+pub fn rect(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    let (left, top, right, bottom) = bounds(start, end);
+    let mut out = Vec::new();
+
+    for x in left..=right {
+        out.push((x, top));
+        out.push((x, bottom));
+    }
+    for y in top..=bottom {
+        out.push((left, y));
+        out.push((right, y));
+    }
+
+    out
+}
+
+/// Every cell inside the rectangle with opposite corners `start` and `end`
+pub fn filled_rect(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    let (left, top, right, bottom) = bounds(start, end);
+
+    (top..=bottom)
+        .flat_map(|y| (left..=right).map(move |x| (x, y)))
+        .collect()
+}
+
+/// Outline of the ellipse that fits inside the rectangle with opposite corners `start` and `end`
+pub fn ellipse(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    let (cx, cy, x_off, y_off, rx, ry) = ellipse_bounds(start, end);
+
+    ellipse_quadrant(rx, ry)
+        .into_iter()
+        .flat_map(|(qx, qy)| {
+            [
+                (cx + qx + x_off, cy + qy + y_off),
+                (cx - qx, cy + qy + y_off),
+                (cx + qx + x_off, cy - qy),
+                (cx - qx, cy - qy),
+            ]
+        })
+        .map(|(x, y)| (x as u16, y as u16))
+        .collect()
+}
+
+/// Every cell inside the ellipse that fits inside the rectangle with opposite corners `start` and `end`
+pub fn filled_ellipse(start: (u16, u16), end: (u16, u16)) -> Vec<(u16, u16)> {
+    let (cx, cy, x_off, y_off, rx, ry) = ellipse_bounds(start, end);
+
+    // The widest point of every row is enough to fill the span between both sides
+    let mut spans = vec![0; ry as usize + 1];
+    for (qx, qy) in ellipse_quadrant(rx, ry) {
+        spans[qy as usize] = spans[qy as usize].max(qx);
+    }
+
+    let mut out = Vec::new();
+    for (qy, &span) in spans.iter().enumerate() {
+        let qy = qy as i32;
+        for x in cx - span..=cx + span + x_off {
+            out.push((x as u16, (cy + qy + y_off) as u16));
+            out.push((x as u16, (cy - qy) as u16));
+        }
+    }
+
+    out
+}
+
+/// Returns the (left, top, right, bottom) edges of the rectangle spanned by two corners
+pub fn bounds(start: (u16, u16), end: (u16, u16)) -> (u16, u16, u16, u16) {
+    (
+        start.0.min(end.0),
+        start.1.min(end.1),
+        start.0.max(end.0),
+        start.1.max(end.1),
+    )
+}
+
+/// Center, radii and the offsets that stretch the right and bottom halves when the
+/// rectangle is an even amount of cells wide or tall, since the center falls between two cells
+fn ellipse_bounds(start: (u16, u16), end: (u16, u16)) -> (i32, i32, i32, i32, i32, i32) {
+    let (left, top, right, bottom) = bounds(start, end);
+    let (width, height) = ((right - left) as i32, (bottom - top) as i32);
+    let (rx, ry) = (width / 2, height / 2);
+
+    (
+        left as i32 + rx,
+        top as i32 + ry,
+        width % 2,
+        height % 2,
+        rx,
+        ry,
+    )
+}
+
+/// Midpoint ellipse rasterization
+///
+/// Returns the outline of the first quadrant as offsets from the center,
+/// the other quadrants are mirror images of it
+pub fn ellipse_quadrant(rx: i32, ry: i32) -> Vec<(i32, i32)> {
+    if rx <= 0 || ry <= 0 {
+        return (0..=rx.max(0))
+            .map(|x| (x, 0))
+            .chain((0..=ry.max(0)).map(|y| (0, y)))
+            .collect();
+    }
+
+    // All decision values are multiplied by 4 to stay in integer math
+    let (rx2, ry2) = (rx as i64 * rx as i64, ry as i64 * ry as i64);
+    let (mut x, mut y) = (0, ry);
+    let (mut px, mut py) = (0, 2 * rx2 * y as i64);
+
+    let mut out = vec![(x, y)];
+
+    // Region 1: the slope is shallower than -1, step along x
+    let mut p = 4 * ry2 - 4 * rx2 * ry as i64 + rx2;
+    while px < py {
+        x += 1;
+        px += 2 * ry2;
+        if p < 0 {
+            p += 4 * (ry2 + px);
+        } else {
+            y -= 1;
+            py -= 2 * rx2;
+            p += 4 * (ry2 + px - py);
+        }
+        out.push((x, y));
+    }
+
+    // Region 2: the slope is steeper than -1, step along y
+    let (xl, yl) = (x as i64, y as i64);
+    p = ry2 * (4 * xl * xl + 4 * xl + 1) + 4 * rx2 * (yl - 1) * (yl - 1) - 4 * rx2 * ry2;
+    while y > 0 {
+        y -= 1;
+        py -= 2 * rx2;
+        if p > 0 {
+            p += 4 * (rx2 - py);
+        } else {
+            x += 1;
+            px += 2 * ry2;
+            p += 4 * (rx2 - py + px);
+        }
+        out.push((x, y));
+    }
+
+    out
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::cell::Cell;
+use super::layers::LayerData;
+use super::transform::Transform;
+
+/// Axes that everything drawn gets mirrored around
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Symmetry {
+    #[default]
+    Off,
+    /// Mirror left to right around a vertical line
+    Vertical,
+    /// Mirror top to bottom around a horizontal line
+    Horizontal,
+    /// Mirror around both lines, making four copies
+    Both,
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Symmetry {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Off => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+            Self::Horizontal => Self::Both,
+            Self::Both => Self::Off,
+        }
+    }
+
+    /// Which copies get made, as (flipped left to right, flipped top to bottom)
+    const fn flips(&self) -> &'static [(bool, bool)] {
+        match self {
+            Self::Off => &[],
+            Self::Vertical => &[(true, false)],
+            Self::Horizontal => &[(false, true)],
+            Self::Both => &[(true, false), (false, true), (true, true)],
+        }
+    }
+
+    /// The mirror images of `cells`, positions that are part of `cells` themselves are left alone.
+    /// `axis` is twice the position of the axes, that way they can also run between two positions.
+    pub fn mirror(&self, (sx, sy): (i32, i32), cells: &LayerData) -> LayerData {
+        let mut out = LayerData::new();
+
+        for (&(x, y), &cell) in cells {
+            for &(flip_x, flip_y) in self.flips() {
+                let mx = if flip_x { sx - x as i32 } else { x as i32 };
+                let my = if flip_y { sy - y as i32 } else { y as i32 };
+                let (Ok(mx), Ok(my)) = (u16::try_from(mx), u16::try_from(my)) else {
+                    continue;
+                };
+                if cells.contains_key(&(mx, my)) {
+                    continue;
+                }
+
+                let mut char = cell.char;
+                if flip_x {
+                    char = Transform::FlipHorizontal.glyph(char);
+                }
+                if flip_y {
+                    char = Transform::FlipVertical.glyph(char);
+                }
+
+                out.insert((mx, my), Cell { char, ..cell });
+            }
+        }
+
+        out
+    }
+}
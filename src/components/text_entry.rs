@@ -0,0 +1,144 @@
+use super::brush::Brush;
+use super::cell::Cell;
+use super::figlet::Font;
+use super::layers::LayerData;
+
+/// Text being typed onto the canvas with the text tool
+#[derive(Debug)]
+pub struct TextEntry {
+    /// Position of the first char, in layer coordinates
+    pub origin: (u16, u16),
+    pub lines: Vec<Vec<char>>,
+    /// Line the caret is on
+    pub row: usize,
+    /// Char in the line the caret is in front of
+    pub col: usize,
+    /// Banner font the text is rendered with, plain text when [None]
+    pub font: Option<Font>,
+}
+
+impl TextEntry {
+    pub fn new(origin: (u16, u16), font: Option<Font>) -> Self {
+        Self {
+            origin,
+            lines: vec![vec![]],
+            row: 0,
+            col: 0,
+            font,
+        }
+    }
+
+    /// Position of the caret in layer coordinates
+    pub fn caret(&self) -> (u16, u16) {
+        let (left, top) = self.origin;
+
+        match &self.font {
+            Some(font) => {
+                let before = font.render(&self.lines[self.row][..self.col]);
+                let width = before.iter().map(Vec::len).max().unwrap_or(0);
+                let y = self.row * font.height + font.baseline - 1;
+                (left + width as u16, top + y as u16)
+            }
+            None => (left + self.col as u16, top + self.row as u16),
+        }
+    }
+
+    pub fn input(&mut self, c: char) {
+        self.lines[self.row].insert(self.col, c);
+        self.col += 1;
+    }
+
+    pub fn newline(&mut self) {
+        let rest = self.lines[self.row].split_off(self.col);
+        self.row += 1;
+        self.col = 0;
+        self.lines.insert(self.row, rest);
+    }
+
+    pub fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            self.lines[self.row].remove(self.col);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.lines[self.row].len();
+            self.lines[self.row].extend(line);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.col < self.lines[self.row].len() {
+            self.lines[self.row].remove(self.col);
+        } else if self.row + 1 < self.lines.len() {
+            let line = self.lines.remove(self.row + 1);
+            self.lines[self.row].extend(line);
+        }
+    }
+
+    pub fn left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.lines[self.row].len();
+        }
+    }
+
+    pub fn right(&mut self) {
+        if self.col < self.lines[self.row].len() {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    pub fn up(&mut self) {
+        if self.row > 0 {
+            self.row -= 1;
+            self.col = self.col.min(self.lines[self.row].len());
+        }
+    }
+
+    pub fn down(&mut self) {
+        if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = self.col.min(self.lines[self.row].len());
+        }
+    }
+
+    pub fn home(&mut self) {
+        self.col = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.col = self.lines[self.row].len();
+    }
+
+    /// The typed text as cells in the brush colors, the blanks of banner text are left out
+    pub fn cells(&self, brush: &Brush) -> LayerData {
+        let (left, top) = self.origin;
+
+        let rows: Vec<Vec<char>> = match &self.font {
+            Some(font) => self.lines.iter().flat_map(|l| font.render(l)).collect(),
+            None => self.lines.clone(),
+        };
+
+        rows.iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(_, &char)| self.font.is_none() || char != ' ')
+                    .map(move |(x, &char)| {
+                        let cell = Cell {
+                            char,
+                            ..brush.as_cell()
+                        };
+                        ((left + x as u16, top + y as u16), cell)
+                    })
+            })
+            .collect()
+    }
+}
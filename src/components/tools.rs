@@ -1,10 +1,16 @@
 use std::fmt;
 
+use ratatui::layout::{Position, Rect};
 use serde::{Deserialize, Serialize};
 
 use crate::app::App;
 
+use super::box_drawing::{self, Weight, DOWN, LEFT, RIGHT, UP};
+use super::brush::Brush;
+use super::cell::Cell;
 use super::layers::LayerData;
+use super::palette;
+use super::shapes;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(u8)]
@@ -19,6 +25,194 @@ pub enum Tools {
     Plus = 7,
     Vertical = 8,
     Horizontal = 9,
+    Line = 10,
+    Rectangle = 11,
+    FilledRectangle = 12,
+    Ellipse = 13,
+    FilledEllipse = 14,
+    Frame = 15,
+    Fill = 16,
+    Picker = 17,
+    Select = 18,
+    Text = 19,
+    Spray = 20,
+    Shade = 21,
+    Gradient = 22,
+    Polyline = 23,
+    Bezier = 24,
+    Connector = 25,
+    Move = 26,
+}
+
+/// What a cell needs to share with the clicked cell to be part of the filled region
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FillMatch {
+    Char,
+    Fg,
+    Bg,
+    #[default]
+    Cell,
+}
+
+impl fmt::Display for FillMatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Char => write!(f, "Char"),
+            Self::Fg => write!(f, "FG"),
+            Self::Bg => write!(f, "BG"),
+            Self::Cell => write!(f, "Cell"),
+        }
+    }
+}
+
+impl FillMatch {
+    pub const fn all() -> [Self; 4] {
+        [Self::Char, Self::Fg, Self::Bg, Self::Cell]
+    }
+
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Char => Self::Fg,
+            Self::Fg => Self::Bg,
+            Self::Bg => Self::Cell,
+            Self::Cell => Self::Char,
+        }
+    }
+
+    pub fn matches(&self, cell: Cell, target: Cell) -> bool {
+        match self {
+            Self::Char => cell.char == target.char,
+            Self::Fg => cell.fg == target.fg,
+            Self::Bg => cell.bg == target.bg,
+            Self::Cell => cell == target,
+        }
+    }
+}
+
+/// Share of the cells under the spray tool that get drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Density {
+    Sparse,
+    #[default]
+    Light,
+    Medium,
+    Heavy,
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}%", self.percent())
+    }
+}
+
+impl Density {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Sparse => Self::Light,
+            Self::Light => Self::Medium,
+            Self::Medium => Self::Heavy,
+            Self::Heavy => Self::Sparse,
+        }
+    }
+
+    pub const fn percent(&self) -> u8 {
+        match self {
+            Self::Sparse => 10,
+            Self::Light => 25,
+            Self::Medium => 50,
+            Self::Heavy => 75,
+        }
+    }
+}
+
+/// Degree of the curves drawn by the bezier tool
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Curve {
+    /// One control point
+    #[default]
+    Quadratic,
+    /// Two control points
+    Cubic,
+}
+
+impl fmt::Display for Curve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Curve {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Quadratic => Self::Cubic,
+            Self::Cubic => Self::Quadratic,
+        }
+    }
+
+    /// Number of control points between the two ends
+    pub const fn controls(&self) -> usize {
+        match self {
+            Self::Quadratic => 1,
+            Self::Cubic => 2,
+        }
+    }
+}
+
+/// Where the picker samples cells from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PickSource {
+    /// All visible layers combined
+    #[default]
+    Canvas,
+    /// Only the active layer
+    Layer,
+}
+
+impl fmt::Display for PickSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl PickSource {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Canvas => Self::Layer,
+            Self::Layer => Self::Canvas,
+        }
+    }
+}
+
+/// Parts of the sampled cell the picker copies into the brush
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PickTarget {
+    #[default]
+    All,
+    Fg,
+    Bg,
+    Char,
+}
+
+impl fmt::Display for PickTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "All"),
+            Self::Fg => write!(f, "FG"),
+            Self::Bg => write!(f, "BG"),
+            Self::Char => write!(f, "Char"),
+        }
+    }
+}
+
+impl PickTarget {
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::All => Self::Fg,
+            Self::Fg => Self::Bg,
+            Self::Bg => Self::Char,
+            Self::Char => Self::All,
+        }
+    }
 }
 
 impl fmt::Display for Tools {
@@ -39,6 +233,23 @@ impl Tools {
             Self::Plus,
             Self::Vertical,
             Self::Horizontal,
+            Self::Line,
+            Self::Rectangle,
+            Self::FilledRectangle,
+            Self::Ellipse,
+            Self::FilledEllipse,
+            Self::Frame,
+            Self::Fill,
+            Self::Picker,
+            Self::Select,
+            Self::Text,
+            Self::Spray,
+            Self::Shade,
+            Self::Gradient,
+            Self::Polyline,
+            Self::Bezier,
+            Self::Connector,
+            Self::Move,
         ]
     }
 
@@ -53,6 +264,23 @@ impl Tools {
             Self::Plus => '🞣',
             Self::Vertical => '|',
             Self::Horizontal => '─',
+            Self::Line => '╱',
+            Self::Rectangle => '▭',
+            Self::FilledRectangle => '▬',
+            Self::Ellipse => '⬭',
+            Self::FilledEllipse => '⬬',
+            Self::Frame => '╔',
+            Self::Fill => '▦',
+            Self::Picker => '⊙',
+            Self::Select => '▢',
+            Self::Text => 'T',
+            Self::Spray => '∴',
+            Self::Shade => '▒',
+            Self::Gradient => '◑',
+            Self::Polyline => '∧',
+            Self::Bezier => '∿',
+            Self::Connector => '↳',
+            Self::Move => '↔',
         }
         .to_string()
     }
@@ -62,7 +290,7 @@ impl Tools {
             Tools::Eraser => eraser_tool(x, y, size, app),
             Tools::Square => square_tool(x, y, size, app),
             Tools::Box => box_tool(x, y, size, app),
-            Tools::Disk => todo!("Disk (filled Circle)"),
+            Tools::Disk => disk_tool(x, y, size, app),
             Tools::Circle => circle_tool(x, y, size, app),
             Tools::Point => {
                 let mut old_cell = LayerData::new();
@@ -72,8 +300,379 @@ impl Tools {
             Tools::Plus => plus(x, y, size, app),
             Tools::Vertical => vert(x, y, size, app),
             Tools::Horizontal => horiz(x, y, size, app),
+            Tools::Fill => fill_tool(x, y, app),
+            Tools::Picker => picker_tool(x, y, app),
+            Tools::Spray => spray_tool(x, y, size, app),
+            Tools::Shade => shade_tool(x, y, size, app),
+            Tools::Line
+            | Tools::Rectangle
+            | Tools::FilledRectangle
+            | Tools::Ellipse
+            | Tools::FilledEllipse
+            | Tools::Frame
+            | Tools::Select
+            | Tools::Text
+            | Tools::Gradient
+            | Tools::Polyline
+            | Tools::Bezier
+            | Tools::Connector
+            | Tools::Move => LayerData::new(),
+        }
+    }
+
+    /// Click tools act once per press and ignore dragging
+    pub const fn is_click(&self) -> bool {
+        matches!(self, Self::Fill | Self::Picker)
+    }
+
+    /// Shape tools are dragged out from an anchor point and previewed until the mouse is released
+    pub const fn is_shape(&self) -> bool {
+        matches!(
+            self,
+            Self::Line
+                | Self::Rectangle
+                | Self::FilledRectangle
+                | Self::Ellipse
+                | Self::FilledEllipse
+                | Self::Frame
+                | Self::Gradient
+                | Self::Connector
+        )
+    }
+
+    /// Path tools place points one click at a time and are previewed until they are finished
+    pub const fn is_path(&self) -> bool {
+        matches!(self, Self::Polyline | Self::Bezier)
+    }
+
+    /// Tools that work with what is already in the cells, they ignore the pixel mode
+    pub const fn is_cell_only(&self) -> bool {
+        matches!(
+            self,
+            Self::Fill | Self::Picker | Self::Shade | Self::Gradient | Self::Move
+        )
+    }
+
+    /// Cells of the shape spanning from `start` to `end`, nothing is written to the canvas
+    ///
+    /// `layer` is the current content of the active layer, for shapes that join up with it
+    pub fn shape(
+        &self,
+        start: (u16, u16),
+        end: (u16, u16),
+        brush: &Brush,
+        layer: &LayerData,
+    ) -> LayerData {
+        match self {
+            Tools::Line => line_shape(start, end, brush),
+            Tools::Rectangle => thicken(shapes::rect(start, end), brush.size, brush.as_cell()),
+            Tools::Ellipse => thicken(shapes::ellipse(start, end), brush.size, brush.as_cell()),
+            Tools::FilledRectangle => fill(shapes::filled_rect(start, end), brush.as_cell()),
+            Tools::FilledEllipse => fill(shapes::filled_ellipse(start, end), brush.as_cell()),
+            Tools::Frame => frame_shape(start, end, brush, layer),
+            Tools::Connector => connector_shape(start, end, brush, layer),
+            _ => LayerData::new(),
+        }
+    }
+
+    /// Cells of the path running through `points`, nothing is written to the canvas
+    pub fn path(&self, points: &[(u16, u16)], brush: &Brush) -> LayerData {
+        match self {
+            Tools::Polyline => thicken(shapes::polyline(points), brush.size, brush.as_cell()),
+            Tools::Bezier => thicken(shapes::bezier(points), brush.size, brush.as_cell()),
+            _ => LayerData::new(),
+        }
+    }
+}
+
+fn line_shape(start: (u16, u16), end: (u16, u16), brush: &Brush) -> LayerData {
+    let mut cell = brush.as_cell();
+
+    if brush.line_glyphs {
+        cell.char = slope_glyph(start, end);
+    }
+
+    thicken(shapes::line(start, end), brush.size, cell)
+}
+
+/// Rectangle made of box drawing glyphs, joining up with the ones already on the layer
+fn frame_shape(start: (u16, u16), end: (u16, u16), brush: &Brush, layer: &LayerData) -> LayerData {
+    let (left, top, right, bottom) = shapes::bounds(start, end);
+    let style = brush.frame_style;
+    let weight = style.weight();
+
+    let mut out = LayerData::new();
+
+    for (x, y) in shapes::rect(start, end) {
+        let horizontal = y == top || y == bottom;
+        let vertical = x == left || x == right;
+
+        let mut arms = [Weight::None; 4];
+        if vertical && y > top {
+            arms[UP] = weight;
+        }
+        if vertical && y < bottom {
+            arms[DOWN] = weight;
+        }
+        if horizontal && x > left {
+            arms[LEFT] = weight;
+        }
+        if horizontal && x < right {
+            arms[RIGHT] = weight;
+        }
+        // A click without dragging still leaves the top left corner of a frame
+        if start == end {
+            arms[DOWN] = weight;
+            arms[RIGHT] = weight;
+        }
+
+        if let Some(old_cell) = layer.get(&(x, y)) {
+            arms = box_drawing::merge(arms, old_cell.char);
+        }
+
+        if let Some(char) = box_drawing::glyph(arms, style) {
+            out.insert(
+                (x, y),
+                Cell {
+                    char,
+                    ..brush.as_cell()
+                },
+            );
         }
     }
+
+    out
+}
+
+/// Line of box drawing glyphs routed around a bend, with arrowheads at its ends.
+/// It joins up with the glyphs already on the layer, so it can leave from the edge of a frame.
+fn connector_shape(
+    start: (u16, u16),
+    end: (u16, u16),
+    brush: &Brush,
+    layer: &LayerData,
+) -> LayerData {
+    let style = brush.frame_style;
+    let weight = style.weight();
+    let route = shapes::elbow(start, end);
+
+    // Side of `from` that `to` is on, the points of the route are always next to each other
+    let side = |from: (u16, u16), to: (u16, u16)| {
+        if to.0 > from.0 {
+            RIGHT
+        } else if to.0 < from.0 {
+            LEFT
+        } else if to.1 > from.1 {
+            DOWN
+        } else {
+            UP
+        }
+    };
+
+    let mut out = LayerData::new();
+    let last = route.len() - 1;
+
+    for (i, &pos) in route.iter().enumerate() {
+        let mut arms = [Weight::None; 4];
+        if i > 0 {
+            arms[side(pos, route[i - 1])] = weight;
+        }
+        if i < last {
+            arms[side(pos, route[i + 1])] = weight;
+        }
+
+        // The heads point away from the line
+        let head = match i {
+            _ if last == 0 => None,
+            0 => brush.start_head.glyph(side(route[1], pos)),
+            _ if i == last => brush.end_head.glyph(side(route[i - 1], pos)),
+            _ => None,
+        };
+
+        if let Some(old_cell) = layer.get(&pos) {
+            arms = box_drawing::merge(arms, old_cell.char);
+        }
+
+        if let Some(char) = head.or_else(|| box_drawing::glyph(arms, style)) {
+            out.insert(
+                pos,
+                Cell {
+                    char,
+                    ..brush.as_cell()
+                },
+            );
+        }
+    }
+
+    out
+}
+
+/// Picks the box drawing line that best matches the direction from `start` to `end`
+fn slope_glyph(start: (u16, u16), end: (u16, u16)) -> char {
+    let dx = end.0 as f64 - start.0 as f64;
+    // Cells are about twice as tall as they are wide
+    let dy = (end.1 as f64 - start.1 as f64) * 2.0;
+
+    if dx == 0.0 && dy == 0.0 {
+        return '─';
+    }
+
+    // tan(22.5°) and tan(67.5°) split the directions into horizontal, diagonal and vertical
+    let slope = dy.abs() / dx.abs();
+    if slope < 0.414 {
+        '─'
+    } else if slope > 2.414 {
+        '│'
+    } else if (dx > 0.0) == (dy > 0.0) {
+        '╲'
+    } else {
+        '╱'
+    }
+}
+
+fn fill(points: Vec<(u16, u16)>, cell: Cell) -> LayerData {
+    points.into_iter().map(|pos| (pos, cell)).collect()
+}
+
+/// Widens every point to the size of the brush
+fn thicken(points: Vec<(u16, u16)>, size: u16, cell: Cell) -> LayerData {
+    let mut out = LayerData::new();
+
+    for (x, y) in points {
+        let (left, right, bottom, top) = get_brush_rect_i16(x, y, size);
+        for fx in left..right {
+            for fy in bottom..top {
+                if fx < 0 || fy < 0 {
+                    continue;
+                }
+                out.insert((fx as u16, fy as u16), cell);
+            }
+        }
+    }
+
+    out
+}
+
+/// Flood fills the region around (x, y), empty space is bounded by the canvas
+fn fill_tool(x: u16, y: u16, app: &mut App) -> LayerData {
+    let layer = &app.layers.get_active_layer().data;
+    let region = flood_region((x, y), layer, app.brush.fill_match, app.canvas_area);
+
+    let mut old_cells = LayerData::new();
+    for (x, y) in region {
+        old_cells.insert((x, y), app.put_cell(x, y));
+    }
+    old_cells
+}
+
+/// The connected cells around `start` that match it by `criteria`, staying inside of `bounds`
+pub fn flood_region(
+    start: (u16, u16),
+    layer: &LayerData,
+    criteria: FillMatch,
+    bounds: Rect,
+) -> Vec<(u16, u16)> {
+    let target = layer.get(&start).copied().unwrap_or_default();
+
+    let mut region = Vec::new();
+    let mut visited = hashbrown::HashSet::new();
+    let mut stack = vec![start];
+
+    while let Some((x, y)) = stack.pop() {
+        if !bounds.contains(Position { x, y }) || !visited.insert((x, y)) {
+            continue;
+        }
+
+        let cell = layer.get(&(x, y)).copied().unwrap_or_default();
+        if !criteria.matches(cell, target) {
+            continue;
+        }
+
+        region.push((x, y));
+        stack.extend([
+            (x.saturating_sub(1), y),
+            (x + 1, y),
+            (x, y.saturating_sub(1)),
+            (x, y + 1),
+        ]);
+    }
+
+    region
+}
+
+/// Colors the cells of `region` going from the brush fg at `start` to the brush bg at `end`,
+/// nothing when either of them is unset
+///
+/// Dithering only uses the two brush colors and blends them with shading glyphs,
+/// otherwise every cell gets its own color: glyphs keep their char and get it as their fg,
+/// blank and empty cells get it as their bg
+pub fn gradient(
+    region: &[(u16, u16)],
+    start: (u16, u16),
+    end: (u16, u16),
+    brush: &Brush,
+    layer: &LayerData,
+) -> LayerData {
+    const DITHER: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+    if brush.has_unset_color() {
+        return LayerData::new();
+    }
+
+    let (from, to) = (palette::rgb(brush.fg), palette::rgb(brush.bg));
+    let (dx, dy) = (end.0 as f64 - start.0 as f64, end.1 as f64 - start.1 as f64);
+    // Cells are about twice as tall as they are wide
+    let length = dx * dx + dy * dy * 4.0;
+
+    region
+        .iter()
+        .map(|&(x, y)| {
+            let (px, py) = (x as f64 - start.0 as f64, y as f64 - start.1 as f64);
+            let t = if length == 0.0 {
+                0.0
+            } else {
+                ((px * dx + py * dy * 4.0) / length).clamp(0.0, 1.0)
+            };
+
+            let cell = if brush.dither {
+                Cell {
+                    char: DITHER[(t * 4.0).round() as usize],
+                    fg: brush.quantize.color(to),
+                    bg: brush.quantize.color(from),
+                }
+            } else {
+                let color = brush.quantize.color(palette::lerp(from, to, t));
+                match layer.get(&(x, y)) {
+                    Some(&cell) if cell.char != ' ' => Cell { fg: color, ..cell },
+                    Some(&cell) => Cell {
+                        fg: color,
+                        bg: color,
+                        ..cell
+                    },
+                    None => Cell {
+                        char: ' ',
+                        fg: color,
+                        bg: color,
+                    },
+                }
+            };
+            ((x, y), cell)
+        })
+        .collect()
+}
+
+/// Copies the cell under the cursor into the brush, the canvas is left untouched
+fn picker_tool(x: u16, y: u16, app: &mut App) -> LayerData {
+    let cell = match app.brush.pick_source {
+        PickSource::Canvas => app.layers.render().get(&(x, y)).copied(),
+        PickSource::Layer => app.layers.get_active_layer().data.get(&(x, y)).copied(),
+    };
+
+    if let Some(cell) = cell {
+        app.brush.pick(cell);
+    }
+
+    LayerData::new()
 }
 
 pub fn plus(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
@@ -93,12 +692,12 @@ pub fn plus(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
             if x_arm >= 0 {
                 let x_arm = x_arm as u16;
                 // old_cells.insert((x_arm, y), app.draw(x_arm, y));
-                old_cells.insert((x, y), app.put_cell(x_arm, y));
+                old_cells.insert((x_arm, y), app.put_cell(x_arm, y));
             }
             if y_arm >= 0 {
                 let y_arm = y_arm as u16;
                 // old_cells.insert((x, y_arm), app.draw(x, y_arm));
-                old_cells.insert((x, y), app.put_cell(x, y_arm));
+                old_cells.insert((x, y_arm), app.put_cell(x, y_arm));
             }
         }
     }
@@ -122,7 +721,7 @@ pub fn horiz(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
             if x_arm >= 0 {
                 let x_arm = x_arm as u16;
                 // old_cells.insert((x_arm, y), app.draw(x_arm, y));
-                old_cells.insert((x, y), app.put_cell(x_arm, y));
+                old_cells.insert((x_arm, y), app.put_cell(x_arm, y));
             }
         }
     }
@@ -146,7 +745,7 @@ pub fn vert(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
             if y_arm >= 0 {
                 let y_arm = y_arm as u16;
                 // old_cells.insert((x, y_arm), app.draw(x, y_arm));
-                old_cells.insert((x, y), app.put_cell(x, y_arm));
+                old_cells.insert((x, y_arm), app.put_cell(x, y_arm));
             }
         }
     }
@@ -154,69 +753,154 @@ pub fn vert(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
     old_cells
 }
 
-// fn disk_tool(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
-//     let mut old_cells = LayerData::new();
-//     let (x, y) = (x as i16, y as i16);
-//
-//     let mut x_r = size as i16;
-//     let mut y_r = 0;
-//     let mut p = 1 - x_r;
-//
-//     // if self.size > 0 {
-//     app.draw(x + x_r, y);
-//     app.draw(x - x_r, y);
-//     app.draw(x, y + x_r);
-//     app.draw(x, y - x_r);
-//     // }
-//
-//     while x_r > y_r {
-//         y_r += 1;
-//         if p <= 0 {
-//             p = p + 2 * y_r + 1;
-//         } else {
-//             x_r -= 1;
-//             p = p + 2 * y_r - 2 * x_r + 1;
-//         }
-//
-//         if x_r < y_r {
-//             break;
-//         }
-//
-//         // Draw the points at the circumference
-//         app.draw(x + x_r, y + y_r);
-//         app.draw(x - x_r, y + y_r);
-//         app.draw(x + x_r, y - y_r);
-//         app.draw(x - x_r, y - y_r);
-//
-//         if x_r != y_r {
-//             app.draw(x + y_r, y + x_r);
-//             app.draw(x - y_r, y + x_r);
-//             app.draw(x + y_r, y - x_r);
-//             app.draw(x - y_r, y - x_r);
-//         }
-//     }
-//     old_cells
-// }
+fn disk_tool(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
+    put_offsets(x, y, disk_offsets(size), app)
+}
 
-fn circle_tool(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
+/// Scatters cells over the area of the disk tool, picking the glyphs from the spray chars
+fn spray_tool(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
+    let density = app.brush.spray_density.percent() as u32;
     let mut old_cells = LayerData::new();
 
-    let (cx, cy, radius) = (x as i16, y as i16, size as i16);
+    for (ox, oy) in disk_offsets(size) {
+        let (fx, fy) = (x as i32 + ox, y as i32 + oy);
+        let (Ok(fx), Ok(fy)) = (u16::try_from(fx), u16::try_from(fy)) else {
+            continue;
+        };
 
-    for y in -radius..=radius {
-        for x in -radius..=radius {
-            if x * x + y * y <= radius * radius {
-                let (fx, fy) = (cx + x, cy + y);
-                if fx < 0 || fy < 0 {
-                    continue;
-                }
-                let (fx, fy) = (fx as u16, fy as u16);
-                // old_cells.insert((fx, fy), app.draw(fx, fy));
-                old_cells.insert((fx, fy), app.put_cell(fx, fy));
+        if old_cells.contains_key(&(fx, fy)) || alea::u32_less_than(100) >= density {
+            continue;
+        }
+
+        let mut cell = app.brush.as_cell();
+        if !app.spray_chars.is_empty() {
+            cell.char = app.spray_chars[alea::u32_less_than(app.spray_chars.len() as u32) as usize];
+        }
+        old_cells.insert((fx, fy), app.insert_at_cell(fx, fy, cell));
+    }
+
+    old_cells
+}
+
+/// Moves the glyphs under the brush one step along the shade ramp, or back while holding Shift or Alt
+fn shade_tool(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
+    let mut old_cells = LayerData::new();
+    let (left, right, bottom, top) = get_brush_rect_i16(x, y, size);
+
+    for x in left..right {
+        for y in bottom..top {
+            if x < 0 || y < 0 {
+                continue;
+            }
+            let pos = (x as u16, y as u16);
+
+            if old_cells.contains_key(&pos) {
+                continue;
+            }
+
+            // Cells only take one step per stroke, no matter how often the brush passes over them
+            let layer = &app.layers.get_active_layer().data;
+            let cell = *app
+                .shaded
+                .entry(pos)
+                .or_insert_with(|| layer.get(&pos).copied().unwrap_or_default());
+
+            let Some(new_cell) = shade(cell, &app.ramp, app.shade_down, &app.brush) else {
+                continue;
+            };
+            let old_cell = if new_cell == Cell::default() {
+                app.erase(pos.0, pos.1)
+            } else {
+                app.insert_at_cell(pos.0, pos.1, new_cell)
+            };
+            old_cells.insert(pos, old_cell);
+        }
+    }
+
+    old_cells
+}
+
+/// The cell one step up or down `ramp`, [None] when it stays as it is
+///
+/// Empty cells start at the bottom of the ramp in the brush colors, glyphs that are not on it
+/// are left alone
+fn shade(cell: Cell, ramp: &[char], down: bool, brush: &Brush) -> Option<Cell> {
+    let empty = cell == Cell::default();
+
+    let step = match (ramp.iter().position(|&c| c == cell.char), down) {
+        (None, false) if empty => 0,
+        (None, _) => return None,
+        (Some(0), true) => return (!empty).then(Cell::default),
+        (Some(i), true) => i - 1,
+        (Some(i), false) => i + 1,
+    };
+
+    let char = *ramp.get(step)?;
+    let base = if empty { brush.as_cell() } else { cell };
+    Some(Cell { char, ..base })
+}
+
+/// Every offset from the center covered by the disk tool, the middle row is in there twice
+fn disk_offsets(size: u16) -> Vec<(i32, i32)> {
+    let (rx, ry) = brush_radii(size);
+    let mut offsets = Vec::new();
+
+    // The widest point of every row is enough to fill the span between both sides
+    let mut spans = vec![0; ry as usize + 1];
+    for (qx, qy) in shapes::ellipse_quadrant(rx, ry) {
+        spans[qy as usize] = spans[qy as usize].max(qx);
+    }
+
+    for (qy, &span) in spans.iter().enumerate() {
+        let qy = qy as i32;
+        for qx in -span..=span {
+            offsets.push((qx, qy));
+            if qy != 0 {
+                offsets.push((qx, -qy));
             }
         }
     }
 
+    offsets
+}
+
+fn circle_tool(x: u16, y: u16, size: u16, app: &mut App) -> LayerData {
+    let (rx, ry) = brush_radii(size);
+
+    let offsets = shapes::ellipse_quadrant(rx, ry)
+        .into_iter()
+        .flat_map(|(qx, qy)| [(qx, qy), (-qx, qy), (qx, -qy), (-qx, -qy)])
+        .collect();
+
+    put_offsets(x, y, offsets, app)
+}
+
+/// Horizontal and vertical radius for the round tools
+///
+/// Terminal cells are roughly twice as tall as they are wide,
+/// so the vertical radius is halved to keep the shape looking round
+const fn brush_radii(size: u16) -> (i32, i32) {
+    let rx = size as i32;
+    let ry = (rx + 1) / 2;
+    (rx, ry)
+}
+
+/// Draws the brush at every offset from (x, y), skipping duplicates and negative positions
+fn put_offsets(x: u16, y: u16, offsets: Vec<(i32, i32)>, app: &mut App) -> LayerData {
+    let mut old_cells = LayerData::new();
+
+    for (ox, oy) in offsets {
+        let (fx, fy) = (x as i32 + ox, y as i32 + oy);
+        let (Ok(fx), Ok(fy)) = (u16::try_from(fx), u16::try_from(fy)) else {
+            continue;
+        };
+
+        if old_cells.contains_key(&(fx, fy)) {
+            continue;
+        }
+        old_cells.insert((fx, fy), app.put_cell(fx, fy));
+    }
+
     old_cells
 }
 
@@ -0,0 +1,112 @@
+use super::box_drawing::{self, Arms, FrameStyle, DOWN, LEFT, RIGHT, UP};
+use super::selection::Area;
+
+/// Ways to turn the cells of an area around
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transform {
+    /// Mirror left to right
+    FlipHorizontal,
+    /// Mirror top to bottom
+    FlipVertical,
+    /// Turn 90° clockwise
+    Rotate,
+    /// Mirror along the diagonal from the top left corner
+    Transpose,
+}
+
+/// Glyphs that turn into each other when mirrored left to right
+#[rustfmt::skip]
+const FLIP_HORIZONTAL: &[(char, char)] = &[
+    ('/', '\\'), ('╱', '╲'), ('(', ')'), ('[', ']'), ('{', '}'), ('<', '>'), ('«', '»'),
+    ('▌', '▐'), ('▏', '▕'), ('▘', '▝'), ('▖', '▗'), ('▛', '▜'), ('▙', '▟'), ('▚', '▞'),
+    ('◢', '◣'), ('◥', '◤'), ('◀', '▶'), ('←', '→'), ('↖', '↗'), ('↙', '↘'),
+    ('╭', '╮'), ('╰', '╯'), ('⌐', '¬'), ('b', 'd'), ('p', 'q'),
+];
+
+/// Glyphs that turn into each other when mirrored top to bottom
+#[rustfmt::skip]
+const FLIP_VERTICAL: &[(char, char)] = &[
+    ('/', '\\'), ('╱', '╲'), ('▀', '▄'), ('▔', '▁'), ('^', 'v'), ('\'', ','),
+    ('▘', '▖'), ('▝', '▗'), ('▛', '▙'), ('▜', '▟'), ('▚', '▞'),
+    ('◢', '◥'), ('◣', '◤'), ('▲', '▼'), ('↑', '↓'), ('↖', '↙'), ('↗', '↘'),
+    ('╭', '╰'), ('╮', '╯'), ('b', 'p'), ('d', 'q'),
+];
+
+/// Glyphs that turn into each other when mirrored along the diagonal
+#[rustfmt::skip]
+const TRANSPOSE: &[(char, char)] = &[
+    ('-', '|'), ('─', '│'), ('━', '┃'), ('═', '║'),
+    ('┄', '┆'), ('┅', '┇'), ('┈', '┊'), ('┉', '┋'), ('╌', '╎'), ('╍', '╏'),
+    ('▀', '▌'), ('▄', '▐'), ('▔', '▏'), ('▁', '▕'), ('▝', '▖'), ('▜', '▙'),
+    ('◥', '◣'), ('▶', '▼'), ('◀', '▲'), ('→', '↓'), ('←', '↑'), ('↗', '↙'),
+    ('╮', '╰'),
+];
+
+impl Transform {
+    /// Where a position inside `area` ends up
+    pub const fn position(
+        &self,
+        (left, top, right, bottom): Area,
+        (x, y): (u16, u16),
+    ) -> (u16, u16) {
+        match self {
+            Self::FlipHorizontal => (left + right - x, y),
+            Self::FlipVertical => (x, top + bottom - y),
+            Self::Rotate => (left + bottom - y, top + x - left),
+            Self::Transpose => (left + y - top, top + x - left),
+        }
+    }
+
+    /// The area covered after transforming, it keeps its top left corner
+    pub const fn area(&self, area: Area) -> Area {
+        let (left, top, right, bottom) = area;
+        match self {
+            Self::FlipHorizontal | Self::FlipVertical => area,
+            Self::Rotate | Self::Transpose => (left, top, left + bottom - top, top + right - left),
+        }
+    }
+
+    /// The glyph that looks like `c` after transforming, or `c` itself when it is symmetric
+    pub fn glyph(&self, c: char) -> char {
+        match self {
+            Self::FlipHorizontal => swap(FLIP_HORIZONTAL, c).unwrap_or_else(|| turn(c, *self)),
+            Self::FlipVertical => swap(FLIP_VERTICAL, c).unwrap_or_else(|| turn(c, *self)),
+            Self::Transpose => swap(TRANSPOSE, c).unwrap_or_else(|| turn(c, *self)),
+            // Turning clockwise is the same as mirroring along the diagonal and then left to right
+            Self::Rotate => Self::FlipHorizontal.glyph(Self::Transpose.glyph(c)),
+        }
+    }
+}
+
+fn swap(pairs: &[(char, char)], c: char) -> Option<char> {
+    pairs.iter().find_map(|&(a, b)| match c {
+        _ if c == a => Some(b),
+        _ if c == b => Some(a),
+        _ => None,
+    })
+}
+
+/// Swaps the arms of a box drawing glyph
+fn turn(c: char, transform: Transform) -> char {
+    let Some(arms) = box_drawing::arms(c) else {
+        return c;
+    };
+
+    let mut new: Arms = arms;
+    match transform {
+        Transform::FlipHorizontal => new.swap(LEFT, RIGHT),
+        Transform::FlipVertical => new.swap(UP, DOWN),
+        Transform::Transpose => {
+            new.swap(UP, LEFT);
+            new.swap(RIGHT, DOWN);
+        }
+        Transform::Rotate => new.rotate_right(1),
+    }
+
+    // Dashed and other glyphs that only have a solid counterpart stay as they are
+    if new == arms {
+        return c;
+    }
+
+    box_drawing::glyph(new, FrameStyle::Light).unwrap_or(c)
+}
@@ -3,7 +3,11 @@ use crate::components::cell::Cell;
 use crate::components::clicks::*;
 use crate::components::input::{InputMode, MouseMode};
 use crate::components::layers::LayerData;
+use crate::components::pixels::Corner;
 use crate::components::save_load::{FileSaveError, SaveData};
+use crate::components::symmetry::Symmetry;
+use crate::components::tools::Tools;
+use crate::components::transform::Transform;
 use crate::ui::TOOLBOX_WIDTH;
 
 use anstyle::{Ansi256Color, AnsiColor, RgbColor};
@@ -101,6 +105,9 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
         InputMode::Color => color_mode_keymaps(key_event, app),
         InputMode::Export => export_mode_keymaps(key_event, app),
         InputMode::Save => save_mode_keymaps(key_event, app),
+        InputMode::Text => text_mode_keymaps(key_event, app),
+        InputMode::Ramp => ramp_mode_keymaps(key_event, app),
+        InputMode::Replace => replace_mode_keymaps(key_event, app),
         InputMode::Help => match key_event.code {
             KeyCode::Char('c') => {
                 if key_event.modifiers == KeyModifiers::CONTROL {
@@ -109,6 +116,9 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
             }
             KeyCode::Esc | KeyCode::Char('?') => app.input_capture.toggle_help(),
             KeyCode::Char('Q') => app.input_capture.change_mode(InputMode::Exit),
+            // The popup keeps the scroll within the lines that don't fit
+            KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+            KeyCode::Down => app.help_scroll += 1,
             _ => {}
         },
         InputMode::Exit => match key_event.code {
@@ -146,6 +156,7 @@ pub fn handle_mouse_events(event: MouseEvent, app: &mut App) -> AppResult<()> {
 
     match app.input_capture.mode {
         InputMode::Color => color_mode_mouse(event, app, x, y),
+        InputMode::Replace => replace_mode_mouse(event, app, x, y),
         InputMode::Normal => normal_mouse_mode(event, app, x, y)?,
         InputMode::Rename => {
             if event.kind == Down(MouseButton::Left) {
@@ -168,6 +179,25 @@ pub fn handle_mouse_events(event: MouseEvent, app: &mut App) -> AppResult<()> {
             app.input_capture.toggle_help();
             normal_mouse_mode(event, app, x, y)?
         }
+        InputMode::Ramp => {
+            // Clicking anything but the settings of the shade tool finishes the ramp
+            if let Down(_) = event.kind {
+                if !matches!(
+                    app.input_capture.get(x, y),
+                    Some(ClickAction::Next(Increment::ToolOption(_)))
+                ) {
+                    app.input_capture.exit();
+                }
+            }
+            normal_mouse_mode(event, app, x, y)?
+        }
+        InputMode::Text => {
+            // Clicking away finishes the text, the click itself still goes through
+            if let Down(_) = event.kind {
+                app.commit_text();
+                normal_mouse_mode(event, app, x, y)?
+            }
+        }
         InputMode::Export => {
             if event.kind == Down(MouseButton::Left) {
                 if let Some(ClickAction::Export(action)) = app.input_capture.get(x, y) {
@@ -223,6 +253,79 @@ pub fn handle_mouse_events(event: MouseEvent, app: &mut App) -> AppResult<()> {
     Ok(())
 }
 
+fn text_mode_keymaps(key_event: KeyEvent, app: &mut App) {
+    let Some(text) = &mut app.text else {
+        app.input_capture.exit();
+        return;
+    };
+
+    match key_event.code {
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.commit_text();
+            app.input_capture.change_mode(InputMode::Exit);
+            return;
+        }
+        KeyCode::Esc => {
+            app.commit_text();
+            return;
+        }
+        // Shortcuts with other modifiers would otherwise type their letter
+        KeyCode::Char(ch)
+            if matches!(
+                key_event.modifiers,
+                KeyModifiers::NONE | KeyModifiers::SHIFT
+            ) =>
+        {
+            text.input(ch)
+        }
+        KeyCode::Enter => text.newline(),
+        KeyCode::Backspace => text.backspace(),
+        KeyCode::Delete => text.delete(),
+        KeyCode::Left => text.left(),
+        KeyCode::Right => text.right(),
+        KeyCode::Up => text.up(),
+        KeyCode::Down => text.down(),
+        KeyCode::Home => text.home(),
+        KeyCode::End => text.end(),
+        _ => {}
+    }
+
+    app.preview_text();
+}
+
+fn ramp_mode_keymaps(key_event: KeyEvent, app: &mut App) {
+    let ramp = &mut app.ramp;
+    let caret = &mut app.ramp_caret;
+
+    match key_event.code {
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.input_capture.change_mode(InputMode::Exit);
+        }
+        KeyCode::Enter | KeyCode::Esc => app.input_capture.exit(),
+        KeyCode::Char(ch)
+            if matches!(
+                key_event.modifiers,
+                KeyModifiers::NONE | KeyModifiers::SHIFT
+            ) =>
+        {
+            ramp.insert(*caret, ch);
+            *caret += 1;
+        }
+        KeyCode::Backspace if *caret > 0 => {
+            *caret -= 1;
+            ramp.remove(*caret);
+        }
+        KeyCode::Delete if *caret < ramp.len() => {
+            ramp.remove(*caret);
+        }
+        KeyCode::Left => *caret = caret.saturating_sub(1),
+        KeyCode::Right => *caret = (*caret + 1).min(ramp.len()),
+        KeyCode::Home => *caret = 0,
+        KeyCode::End => *caret = ramp.len(),
+        _ => {}
+    }
+}
+
 fn save_mode_keymaps(key_event: KeyEvent, app: &mut App) {
     match key_event.code {
         KeyCode::Char('c') => {
@@ -306,6 +409,19 @@ fn color_mode_keymaps(key_event: KeyEvent, app: &mut App) {
     }
 }
 
+fn replace_mode_keymaps(key_event: KeyEvent, app: &mut App) {
+    match key_event.code {
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.input_capture.change_mode(InputMode::Exit)
+        }
+        KeyCode::Char('Q') => app.input_capture.change_mode(InputMode::Exit),
+        KeyCode::Char('s') => app.replace.swap(),
+        KeyCode::Esc => app.input_capture.exit(),
+        KeyCode::Enter => accept_replace(app),
+        _ => {}
+    }
+}
+
 fn rename_mode_keymaps(key_event: KeyEvent, app: &mut App) {
     match key_event.code {
         KeyCode::Char('c') => {
@@ -338,15 +454,79 @@ fn rename_mode_keymaps(key_event: KeyEvent, app: &mut App) {
 }
 
 fn normal_mode_keymaps(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
+    // Selected cells moved with the arrows are put down by anything but moving them further
+    if !matches!(
+        key_event.code,
+        KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down
+    ) {
+        app.stamp_selection();
+    }
+
     match key_event.code {
+        // Finish or throw away the polyline or curve being placed
+        KeyCode::Enter => app.commit_path(),
+        KeyCode::Esc if !app.path.is_empty() => app.cancel_path(),
+        // Drop the selection on `ESC`, when there is one
+        KeyCode::Esc if app.selection.area.is_some() => app.selection.clear(),
         // Exit application on `ESC` or `Q`
         KeyCode::Esc | KeyCode::Char('Q') => app.input_capture.change_mode(InputMode::Exit),
         // Exit application on `Ctrl-C`
         KeyCode::Char('c' | 'C') => {
             if key_event.modifiers == KeyModifiers::CONTROL {
                 app.input_capture.change_mode(InputMode::Exit)
+            } else if key_event.code == KeyCode::Char('c') {
+                app.copy_selection()
             }
         }
+        // Selection
+        KeyCode::Char('x') => {
+            let old_cells = app.cut_selection();
+            record_draw(app, old_cells);
+        }
+        KeyCode::Char('v') => {
+            let old_cells = app.paste_selection();
+            record_draw(app, old_cells);
+        }
+        KeyCode::Delete | KeyCode::Backspace => {
+            let old_cells = app.delete_selection();
+            record_draw(app, old_cells);
+        }
+        KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down => {
+            let (dx, dy) = match key_event.code {
+                KeyCode::Left => (-1, 0),
+                KeyCode::Right => (1, 0),
+                KeyCode::Up => (0, -1),
+                _ => (0, 1),
+            };
+            // Without a selection the whole layer moves
+            if app.selection.area.is_some() {
+                app.move_selection(dx, dy);
+            } else {
+                app.move_layer(dx, dy);
+            }
+        }
+        // Duplicate, merge down and flatten layers
+        KeyCode::Char('d') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.duplicate_active_layer()
+        }
+        KeyCode::Char('j') => app.merge_down(),
+        KeyCode::Char('J') => app.flatten(),
+        // Replace colors or glyphs
+        KeyCode::Char('r') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.replace.no_selection = false;
+            app.input_capture.change_mode(InputMode::Replace)
+        }
+        // Flip and turn the selection, or the whole layer
+        KeyCode::Char(c @ ('m' | 'M' | 'r' | 't')) => {
+            let transform = match c {
+                'm' => Transform::FlipHorizontal,
+                'M' => Transform::FlipVertical,
+                'r' => Transform::Rotate,
+                _ => Transform::Transpose,
+            };
+            let old_cells = app.transform_selection(transform);
+            record_draw(app, old_cells);
+        }
         // Reset
         KeyCode::Char('R') => app.reset(),
         KeyCode::Char('s') => {
@@ -376,6 +556,18 @@ fn normal_mode_keymaps(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
         // Cycle background color through palette
         KeyCode::Char('b') => app.brush.bg = app.palette.bg_next(),
         KeyCode::Char('B') => app.brush.bg = app.palette.bg_prev(),
+        // Mirror drawing
+        KeyCode::Char('a') => app.brush.symmetry = app.brush.symmetry.next(),
+        KeyCode::Char('A') => app.brush.axis = None,
+        // Draw on pixels smaller than a cell
+        KeyCode::Char('g') => {
+            app.brush.pixel_mode = app.brush.pixel_mode.next();
+            app.layers.last_pos = None;
+        }
+        KeyCode::Char('G') => app.picked_pixel = app.brush.pixel_mode.next_pixel(app.picked_pixel),
+        // Cycle the setting of the current tool
+        KeyCode::Char('o') => app.next_option(0),
+        KeyCode::Char('O') => app.next_option(1),
         // Copy canvas contents to clipboard
         KeyCode::Char('Y') => copy_canvas_text(app)?,
         KeyCode::Char('y') => copy_canvas_ansi(app)?,
@@ -399,6 +591,25 @@ fn normal_mode_keymaps(key_event: KeyEvent, app: &mut App) -> AppResult<()> {
     Ok(())
 }
 
+/// Terminals only report whole cells, so the mouse button picks the pixel inside of one
+const fn pixel_corner(btn: MouseButton) -> Corner {
+    match btn {
+        MouseButton::Right => Corner::Opposite,
+        _ => Corner::Picked,
+    }
+}
+
+/// Holding Shift or Alt makes the shade tool step down its ramp
+const fn shade_down(modifiers: KeyModifiers) -> bool {
+    modifiers.intersects(KeyModifiers::SHIFT.union(KeyModifiers::ALT))
+}
+
+/// Records the replaced cells of the active layer as one history entry
+fn record_draw(app: &mut App, old_cells: LayerData) {
+    let layer_id = app.layers.get_active_layer().id;
+    app.history.draw(layer_id, old_cells);
+}
+
 fn color_mode_mouse(event: MouseEvent, app: &mut App, x: u16, y: u16) {
     if event.kind == Down(MouseButton::Left) || event.kind == Drag(MouseButton::Left) {
         if let Some(&ClickAction::PickColor(action)) = app.input_capture.get(x, y) {
@@ -428,9 +639,44 @@ fn color_mode_mouse(event: MouseEvent, app: &mut App, x: u16, y: u16) {
     }
 }
 
+/// Closes the replace popup once the replace went through, it stays open to show why it didn't
+fn accept_replace(app: &mut App) {
+    if app.apply_replace() {
+        app.input_capture.exit();
+    }
+}
+
+fn replace_mode_mouse(event: MouseEvent, app: &mut App, x: u16, y: u16) {
+    if event.kind != Down(MouseButton::Left) {
+        return;
+    }
+
+    let Some(&ClickAction::Replace(action)) = app.input_capture.get(x, y) else {
+        app.input_capture.exit();
+        return;
+    };
+
+    match action {
+        ReplaceAction::By(by) => app.replace.by = by,
+        ReplaceAction::Scope(scope) => {
+            app.replace.scope = scope;
+            app.replace.no_selection = false;
+        }
+        ReplaceAction::FindBrush => app.replace.find = app.brush.as_cell(),
+        ReplaceAction::WithBrush => app.replace.with = app.brush.as_cell(),
+        ReplaceAction::Swap => app.replace.swap(),
+        ReplaceAction::Accept => accept_replace(app),
+        ReplaceAction::Deny => app.input_capture.exit(),
+        ReplaceAction::Nothing => {}
+    }
+}
+
 fn normal_mouse_mode(event: MouseEvent, app: &mut App, x: u16, y: u16) -> AppResult<()> {
     match event.kind {
         Down(btn) => {
+            // Any click puts down the selected cells that were moved with the arrows
+            app.stamp_selection();
+
             if let Some(&action) = app.input_capture.get(x, y) {
                 let count = match event.modifiers {
                     KeyModifiers::CONTROL => 5,
@@ -440,12 +686,49 @@ fn normal_mouse_mode(event: MouseEvent, app: &mut App, x: u16, y: u16) -> AppRes
 
                 match action {
                     ClickAction::Draw => {
+                        app.corner = pixel_corner(btn);
+                        app.shade_down = shade_down(event.modifiers);
+
                         if btn == MouseButton::Middle {
                             let (old_cells, id) = paste_into_canvas(app, x - TOOLBOX_WIDTH, y)?;
                             app.history.draw(id, old_cells);
                             return Ok(());
                         }
 
+                        // Alt steps the shade tool down its ramp instead
+                        if event.modifiers == KeyModifiers::ALT
+                            && app.brush.symmetry != Symmetry::Off
+                            && app.brush.tool != Tools::Shade
+                        {
+                            app.brush.axis = Some((x - TOOLBOX_WIDTH, y));
+                            return Ok(());
+                        }
+
+                        if app.brush.tool.is_shape() {
+                            app.start_shape(x, y);
+                            return Ok(());
+                        }
+
+                        if app.brush.tool.is_path() {
+                            app.click_path(x, y);
+                            return Ok(());
+                        }
+
+                        if app.brush.tool == Tools::Move {
+                            app.start_move(x, y);
+                            return Ok(());
+                        }
+
+                        if app.brush.tool == Tools::Select {
+                            app.start_selection(x, y);
+                            return Ok(());
+                        }
+
+                        if app.brush.tool == Tools::Text {
+                            app.start_text(x, y);
+                            return Ok(());
+                        }
+
                         app.input_capture.mouse_mode = MouseMode::Click;
 
                         // let drawn_cells = draw_wrapper(x, y, app);
@@ -457,13 +740,18 @@ fn normal_mouse_mode(event: MouseEvent, app: &mut App, x: u16, y: u16) -> AppRes
                     ClickAction::Next(i) => match i {
                         Increment::CharPicker => app.char_picker.next(),
                         Increment::BrushSize => app.brush.up(count),
+                        Increment::ToolOption(i) => app.next_option(i),
                     },
                     ClickAction::Prev(i) => match i {
                         Increment::CharPicker => app.char_picker.prev(),
                         Increment::BrushSize => app.brush.down(count),
+                        Increment::ToolOption(i) => app.next_option(i),
                     },
                     ClickAction::Set(v) => match v {
-                        SetValue::Tool(t) => app.brush.tool = t,
+                        SetValue::Tool(t) => {
+                            app.commit_path();
+                            app.brush.tool = t;
+                        }
                         SetValue::Char(c) => app.brush.char = c,
                         SetValue::Reset(rv) => match rv {
                             ResetValue::FG => app.brush.fg = Color::Reset,
@@ -486,6 +774,9 @@ fn normal_mouse_mode(event: MouseEvent, app: &mut App, x: u16, y: u16) -> AppRes
                         }
                         LayerAction::Select(index) => app.layers.set_active_layer(index),
                         LayerAction::Remove => app.remove_active_layer(),
+                        LayerAction::Duplicate => app.duplicate_active_layer(),
+                        LayerAction::MergeDown => app.merge_down(),
+                        LayerAction::Flatten => app.flatten(),
                         LayerAction::Rename => app.input_capture.change_mode(InputMode::Rename),
                         LayerAction::MoveUp => {
                             let layer_id = app.layers.get_active_layer().id;
@@ -502,6 +793,14 @@ fn normal_mouse_mode(event: MouseEvent, app: &mut App, x: u16, y: u16) -> AppRes
                             }
                         }
                         LayerAction::ToggleVis(index) => app.layers.toggle_visible(index),
+                        LayerAction::ToggleLock(index) => app.layers.toggle_locked(index),
+                        LayerAction::ToggleAlphaLock(index) => {
+                            app.layers.toggle_alpha_locked(index)
+                        }
+                        LayerAction::CycleBlend(index) => app.layers.cycle_blend(index),
+                        LayerAction::Opacity(index) => {
+                            app.layers.step_opacity(index, btn == MouseButton::Right)
+                        }
                     },
                     ClickAction::PickColor(PickAction::New) => {
                         app.input_capture.change_mode(InputMode::Color)
@@ -511,13 +810,39 @@ fn normal_mouse_mode(event: MouseEvent, app: &mut App, x: u16, y: u16) -> AppRes
             }
         }
 
-        Drag(MouseButton::Left | MouseButton::Right) => {
+        Drag(btn @ (MouseButton::Left | MouseButton::Right)) => {
             if let Some(&action) = app.input_capture.get(x, y) {
                 if action != ClickAction::Draw {
                     // INFO: If the action isnt a draw action
                     // INFO: return early because we only want draw to respond to drag events
                     return Ok(());
                 }
+                app.corner = pixel_corner(btn);
+                app.shade_down = shade_down(event.modifiers);
+
+                if app.brush.tool.is_shape() {
+                    app.preview_shape(x, y);
+                    return Ok(());
+                }
+
+                if app.brush.tool.is_path() {
+                    app.drag_path(x, y);
+                    return Ok(());
+                }
+
+                if app.brush.tool == Tools::Move {
+                    app.drag_move(x, y);
+                    return Ok(());
+                }
+
+                if app.brush.tool == Tools::Select {
+                    app.drag_selection(x, y);
+                    return Ok(());
+                }
+
+                if app.brush.tool.is_click() {
+                    return Ok(());
+                }
 
                 if app.input_capture.mouse_mode == MouseMode::Click {
                     app.history.click_to_partial_draw();
@@ -531,9 +856,19 @@ fn normal_mouse_mode(event: MouseEvent, app: &mut App, x: u16, y: u16) -> AppRes
             }
         }
         Up(MouseButton::Left | MouseButton::Right) => {
+            if app.layers.anchor.is_some() {
+                app.commit_shape();
+            }
+
+            app.release_path();
+            app.end_move();
+
+            app.end_selection();
+
             if event.modifiers != KeyModifiers::CONTROL {
                 app.layers.last_pos = None;
             }
+            app.shaded.clear();
 
             if app.input_capture.mouse_mode == MouseMode::Drag {
                 let layer_id = app.layers.get_active_layer().id;
@@ -3,6 +3,7 @@ use clap_stdin::FileOrStdin;
 use ratatui::style::Color;
 use regex::Regex;
 use terminart::app::{App, AppResult};
+use terminart::components::figlet::Font;
 use terminart::components::save_load::{AnsiData, SaveData};
 use terminart::handler::{handle_key_events, handle_mouse_events};
 use terminart::handler::{Event, EventHandler};
@@ -27,6 +28,10 @@ struct Cli {
     ///
     /// Formats: "rgb(1,2,3)" / "r,g,b" / #ffffff / #fff
     color: Option<Vec<Color>>,
+
+    #[arg(short, long)]
+    /// FIGlet (.flf) fonts to use for banner text, next to the bundled ones
+    font: Option<Vec<String>>,
 }
 
 fn main() -> AppResult<()> {
@@ -84,6 +89,18 @@ fn main() -> AppResult<()> {
             .for_each(|(og_color, user_color)| *og_color = user_color);
     }
 
+    // Loading user fonts
+    for path in cli.font.unwrap_or_default() {
+        match Font::open_file(&path) {
+            Ok(font) => app.fonts.push(font),
+            Err(e) => {
+                println!("Font not readable: {:?} ({})", path, e);
+                app.quit();
+                return Ok(());
+            }
+        }
+    }
+
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(io::stderr());
     let terminal = Terminal::new(backend)?;
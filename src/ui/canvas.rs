@@ -1,26 +1,80 @@
-use ratatui::layout::Rect;
-use ratatui::style::{Color, Style};
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Span;
-use ratatui::widgets::{canvas::Canvas, Block, BorderType, Borders};
+use ratatui::widgets::{block::Title, canvas::Canvas, Block, BorderType, Borders};
 use ratatui::Frame;
 
-use crate::{app::App, components::clicks::ClickAction, ui::DARK_TEXT};
+use crate::components::pixels::{Corner, PixelMode};
+use crate::components::selection::Area;
+use crate::components::symmetry::Symmetry;
+use crate::{app::App, components::clicks::ClickAction, ui::DARK_TEXT, ui::TOOLBOX_WIDTH};
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Length of each dash of the selection outline
+const ANT_LENGTH: usize = 2;
+/// Milliseconds between each step of the selection outline
+const ANT_SPEED: u128 = 250;
 
 pub fn render(app: &mut App, f: &mut Frame, area: Rect) {
-    let block = Block::new()
+    let mut block = Block::new()
         .borders(Borders::all())
         .border_type(BorderType::Rounded)
         .title(" Canvas ")
         .title_style(Style::new().bg(Color::Green).fg(DARK_TEXT));
 
+    let symmetry = app.brush.symmetry;
+    if symmetry != Symmetry::Off {
+        block =
+            block.title(Title::from(format!(" Mirror: {} ", symmetry)).alignment(Alignment::Right));
+    }
+
+    let pixel_mode = app.brush.pixel_mode;
+    if pixel_mode != PixelMode::Off {
+        // The pixel the left button draws on, by itself
+        let picked = pixel_mode.corner(Corner::Picked, app.picked_pixel);
+        let glyph = pixel_mode
+            .paint(None, picked, true, &app.brush)
+            .map_or(' ', |cell| cell.char);
+        block = block.title(
+            Title::from(format!(" Pixels: {} {} ", pixel_mode, glyph)).alignment(Alignment::Right),
+        );
+    }
+
+    if let Some(lock) = app.layers.get_active_layer().lock_label() {
+        block = block.title(Title::from(Span::styled(
+            lock,
+            Style::new().bg(Color::Red).fg(DARK_TEXT),
+        )));
+    }
+
     let block_inner = block.inner(area);
+    app.canvas_area = Rect {
+        x: block_inner.x - TOOLBOX_WIDTH,
+        ..block_inner
+    };
     app.input_capture
         .click_mode_normal(&block_inner, ClickAction::Draw);
 
     let width = block_inner.width as f64;
     let height = block_inner.height as f64;
 
-    let render = app.layers.render();
+    let mut render = app.layers.render();
+    render.extend(app.layers.preview.iter());
+
+    let ants = app.selection.area.map(marching_ants).unwrap_or_default();
+
+    let axis_lines = mirror_axes(symmetry, app.symmetry_axis(), app.canvas_area);
+
+    let caret = app.text.as_ref().map(|text| {
+        let pos = text.caret();
+        let cell = render.get(&pos).copied().unwrap_or_default();
+        (
+            pos,
+            cell.char(),
+            cell.style().add_modifier(Modifier::REVERSED),
+        )
+    });
 
     let canvas = Canvas::default()
         .x_bounds([0.0, width])
@@ -32,8 +86,99 @@ pub fn render(app: &mut App, f: &mut Frame, area: Rect) {
             {
                 c.print(x, height - y, Span::styled(cell.char(), cell.style()));
             }
+
+            // Axes are only shown on empty cells so they never hide the drawing
+            for &(x, y, char) in axis_lines
+                .iter()
+                .filter(|(x, y, _)| !render.contains_key(&(*x, *y)))
+            {
+                c.print(
+                    x as f64,
+                    height - y as f64,
+                    Span::styled(char.to_string(), Style::new().fg(Color::DarkGray)),
+                );
+            }
+
+            for &(x, y, char, style) in &ants {
+                c.print(
+                    x as f64,
+                    height - y as f64,
+                    Span::styled(char.to_string(), style),
+                );
+            }
+
+            if let Some(((x, y), char, style)) = &caret {
+                c.print(
+                    *x as f64,
+                    height - *y as f64,
+                    Span::styled(char.clone(), *style),
+                );
+            }
         });
 
     f.render_widget(block, area);
     f.render_widget(canvas, block_inner);
 }
+
+/// Dashed outline drawn just outside of the selected area, the dashes move along with time.
+/// Edges that would fall before the first row or column of the canvas are left out.
+fn marching_ants(area: Area) -> Vec<(u16, u16, char, Style)> {
+    let (left, top) = (area.0 as i32 - 1, area.1 as i32 - 1);
+    let (right, bottom) = (area.2 as i32 + 1, area.3 as i32 + 1);
+
+    // Clockwise from the top left corner so the dashes travel around the selection
+    let outline = (left..right)
+        .map(|x| (x, top, if x == left { '┌' } else { '─' }))
+        .chain((top..bottom).map(|y| (right, y, if y == top { '┐' } else { '│' })))
+        .chain(
+            (left + 1..=right)
+                .rev()
+                .map(|x| (x, bottom, if x == right { '┘' } else { '─' })),
+        )
+        .chain(
+            (top + 1..=bottom)
+                .rev()
+                .map(|y| (left, y, if y == bottom { '└' } else { '│' })),
+        );
+
+    let step = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| (d.as_millis() / ANT_SPEED) as usize)
+        % (ANT_LENGTH * 2);
+
+    outline
+        .enumerate()
+        .filter_map(|(i, (x, y, char))| {
+            if x < 1 || y < 1 {
+                return None;
+            }
+            let style = if (i + ANT_LENGTH * 2 - step) % (ANT_LENGTH * 2) < ANT_LENGTH {
+                Style::new().fg(Color::White).bg(Color::Black)
+            } else {
+                Style::new().fg(Color::Black).bg(Color::White)
+            };
+
+            Some((x as u16, y as u16, char, style))
+        })
+        .collect()
+}
+
+/// Dotted lines along the symmetry axes inside the canvas
+fn mirror_axes(symmetry: Symmetry, (ax, ay): (u16, u16), area: Rect) -> Vec<(u16, u16, char)> {
+    let vertical = matches!(symmetry, Symmetry::Vertical | Symmetry::Both);
+    let horizontal = matches!(symmetry, Symmetry::Horizontal | Symmetry::Both);
+    let mut lines = vec![];
+
+    if vertical {
+        lines.extend((area.top()..area.bottom()).map(|y| (ax, y, '┊')));
+    }
+    if horizontal {
+        lines.extend((area.left()..area.right()).map(|x| (x, ay, '┈')));
+    }
+    if vertical && horizontal {
+        lines.retain(|&(x, y, _)| (x, y) != (ax, ay));
+        lines.push((ax, ay, '┼'));
+    }
+
+    lines
+}
@@ -11,6 +11,7 @@ mod popup_exit_confirm;
 mod popup_export;
 mod popup_help;
 mod popup_rename;
+mod popup_replace;
 mod popup_save;
 mod screen_too_small;
 mod sidebar;
@@ -48,7 +49,10 @@ pub fn render(app: &mut App, f: &mut Frame) {
         return;
     }
 
-    if terminal_area.width < 70 || terminal_area.height < 30 {
+    // The sidebar panels above the layer manager have fixed heights, so the third row of
+    // tools (from the Text tool) raised the height it needs. The layer manager's three rows
+    // of controls need 35 to leave a row for the layer list.
+    if terminal_area.width < 70 || terminal_area.height < 35 {
         app.input_capture.change_mode(InputMode::TooSmall);
         screen_too_small::show(f);
         return;
@@ -70,7 +74,8 @@ pub fn render(app: &mut App, f: &mut Frame) {
     match app.input_capture.mode {
         InputMode::Rename => popup_rename::show(app, f),
         InputMode::Color => popup_colorpicker::show(app, f),
-        InputMode::Help => popup_help::show(f),
+        InputMode::Replace => popup_replace::show(app, f),
+        InputMode::Help => popup_help::show(app, f),
         InputMode::Export => popup_export::show(app, f),
         InputMode::Save => popup_save::show(app, f),
         InputMode::Exit => popup_exit_confirm::show(app, f),
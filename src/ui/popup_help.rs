@@ -3,6 +3,8 @@ use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::Line;
 use ratatui::widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph};
 
+use crate::app::App;
+
 use super::{centered_box, YELLOW};
 
 const HELP_TEXT: &str = "
@@ -12,9 +14,25 @@ M-Button - (Canvas) Paste into canvas at mouse cursor
 L-Button - (Palette) Set foreground color
 R-Button - (Palette) Set background color
 M-Button - (Palette) Unset selected color (transparent)
+L/R-Btn  - (Layers) Lower / raise layer opacity
     s, S - Brush size
     f, F - Cycle brush fg
     b, B - Cycle brush bg
+    o, O - Cycle first / second tool setting
+    a, A - Cycle mirror drawing / Center mirror axis
+Alt+LBtn - (Canvas) Move mirror axis (not with the Shade tool)
+S/A+Drag - (Canvas) Shade tool steps down the ramp (Shift / Alt)
+    g, G - Cycle pixel drawing / Pixel LBtn draws on (RBtn opposite)
+  Arrows - Move selection (or layer)
+ c, x, v - Copy / Cut / Paste selection
+     Del - Delete selection
+     Esc - Drop selection / Finish typing text
+   Enter - Finish polyline / curve (Esc throws it away)
+    m, M - Flip selection (or layer) horizontally / vertically
+    r, t - Rotate / Transpose selection (or layer)
+Ctrl + R - Replace colors or characters
+Ctrl + D - Duplicate layer
+    j, J - Merge layer down / Flatten all layers
     u, U - Undo / Redo
        y - Copy canvas to clipboard with ANSI codes
        Y - Copy canvas to clipboard as plain text
@@ -25,13 +43,17 @@ Ctrl + E - Export Canvas (layers, palette, and brush)
        ? - Toggle Help
 ";
 
-pub fn show(f: &mut ratatui::Frame) {
+pub fn show(app: &mut App, f: &mut ratatui::Frame) {
     let help_width = 6 + HELP_TEXT.lines().skip(1).fold(0, |a, b| a.max(b.len())) as u16;
     let help_height = 4 + HELP_TEXT.lines().skip(1).count() as u16;
 
-    let help_area = centered_box(help_width, help_height, f.area());
+    // Lines that don't fit on the terminal are scrolled to with the arrow keys
+    let hidden = help_height.saturating_sub(f.area().height);
+    app.help_scroll = app.help_scroll.min(hidden);
+
+    let help_area = centered_box(help_width, help_height - hidden, f.area());
 
-    let help_box = Block::default()
+    let mut help_box = Block::default()
         .title(" HELP ")
         .title_style(Style::new().bold().fg(YELLOW))
         .title_alignment(Alignment::Center)
@@ -39,6 +61,9 @@ pub fn show(f: &mut ratatui::Frame) {
         .borders(Borders::all())
         .border_type(BorderType::Rounded)
         .border_style(Style::new().fg(Color::Yellow));
+    if hidden > 0 {
+        help_box = help_box.title_bottom(Line::from(" ↑↓ Scroll ").centered());
+    }
 
     let help_box_size = help_box.inner(help_area);
 
@@ -47,5 +72,10 @@ pub fn show(f: &mut ratatui::Frame) {
 
     let lines: Vec<_> = HELP_TEXT.lines().skip(1).map(Line::from).collect();
 
-    f.render_widget(Paragraph::new(lines).fg(YELLOW), help_box_size);
+    f.render_widget(
+        Paragraph::new(lines)
+            .scroll((app.help_scroll, 0))
+            .fg(YELLOW),
+        help_box_size,
+    );
 }
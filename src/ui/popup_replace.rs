@@ -0,0 +1,198 @@
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::components::cell::Cell;
+use crate::components::clicks::{ClickAction::Replace, ReplaceAction};
+use crate::components::replace::ReplaceScope;
+use crate::components::tools::FillMatch;
+
+use super::sidebar::Button;
+use super::{centered_box, DARK_TEXT, WHITE};
+
+/// Width of the labels in front of every row
+const LABEL_WIDTH: u16 = 8;
+
+pub fn show(app: &mut App, f: &mut Frame) {
+    let area = f.area();
+    let has_message = app.replace.no_selection;
+    let box_height = if has_message { 13 } else { 11 };
+    let box_width = 44;
+
+    let block_area = centered_box(box_width, box_height, area);
+
+    app.input_capture
+        .click_mode_popup(&block_area, Replace(ReplaceAction::Nothing));
+
+    let block = Block::new()
+        .title(" Replace ")
+        .title_alignment(Alignment::Center)
+        .title_style(Style::new().reversed().bold())
+        .borders(Borders::all())
+        .border_type(BorderType::Rounded);
+
+    let block_inner = block.inner(block_area);
+
+    f.render_widget(Clear, block_area);
+    f.render_widget(block, block_area);
+
+    let rows = Layout::new(
+        Direction::Vertical,
+        vec![Constraint::Length(1); box_height as usize - 2],
+    )
+    .split(block_inner);
+
+    let replace = app.replace;
+
+    let by: Vec<_> = FillMatch::all()
+        .iter()
+        .map(|&by| (by.to_string(), by == replace.by, ReplaceAction::By(by)))
+        .collect();
+    buttons(app, f, rows[1], "Match", &by);
+
+    let scopes: Vec<_> = ReplaceScope::all()
+        .iter()
+        .map(|&s| (s.to_string(), s == replace.scope, ReplaceAction::Scope(s)))
+        .collect();
+    buttons(app, f, rows[2], "Scope", &scopes);
+
+    cell_row(
+        app,
+        f,
+        rows[4],
+        "Find",
+        replace.find,
+        ReplaceAction::FindBrush,
+    );
+    cell_row(
+        app,
+        f,
+        rows[5],
+        "With",
+        replace.with,
+        ReplaceAction::WithBrush,
+    );
+
+    let swap_area = Rect {
+        x: rows[5].x + rows[5].width - 8,
+        width: 6,
+        ..rows[5]
+    };
+    app.input_capture
+        .click_mode_popup(&swap_area, Replace(ReplaceAction::Swap));
+    f.render_widget(
+        Paragraph::new(Line::from(Button::normal("Swap"))),
+        swap_area,
+    );
+
+    if has_message {
+        f.render_widget(
+            Paragraph::new(Line::from(
+                Span::from(" Nothing is selected ").bg(Color::Red).fg(WHITE),
+            ))
+            .alignment(Alignment::Center),
+            rows[7],
+        );
+        control_buttons(app, f, rows[9]);
+    } else {
+        control_buttons(app, f, rows[7]);
+    }
+}
+
+/// A label followed by a row of buttons, the chosen one is highlighted
+fn buttons(
+    app: &mut App,
+    f: &mut Frame,
+    area: Rect,
+    label: &str,
+    buttons: &[(String, bool, ReplaceAction)],
+) {
+    f.render_widget(Paragraph::new(format!("  {}", label)).bold(), area);
+
+    let mut x = area.x + LABEL_WIDTH;
+    for (label, selected, action) in buttons {
+        let width = label.chars().count() as u16 + 2;
+        let button_area = Rect { x, width, ..area };
+
+        let button = if *selected {
+            Button::selected(label)
+        } else {
+            Button::normal(label)
+        };
+
+        app.input_capture
+            .click_mode_popup(&button_area, Replace(*action));
+        f.render_widget(Paragraph::new(Line::from(button)), button_area);
+
+        x += width;
+    }
+}
+
+/// Preview of a cell with a button to take it from the brush
+fn cell_row(
+    app: &mut App,
+    f: &mut Frame,
+    area: Rect,
+    label: &str,
+    cell: Cell,
+    action: ReplaceAction,
+) {
+    let line = Line::from(vec![
+        Span::from(format!("  {:<1$}", label, LABEL_WIDTH as usize - 2)).bold(),
+        Span::raw("▐").fg(Color::DarkGray),
+        Span::styled(cell.char(), cell.style()),
+        Span::raw("▌").fg(Color::DarkGray),
+    ]);
+    f.render_widget(Paragraph::new(line), area);
+
+    let button_area = Rect {
+        x: area.x + LABEL_WIDTH + 4,
+        width: 12,
+        ..area
+    };
+    app.input_capture
+        .click_mode_popup(&button_area, Replace(action));
+    f.render_widget(
+        Paragraph::new(Line::from(Button::normal("From brush"))),
+        button_area,
+    );
+}
+
+fn control_buttons(app: &mut App, f: &mut Frame, area: Rect) {
+    let layout = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Min(0),
+            Constraint::Length(8),
+            Constraint::Length(4),
+            Constraint::Length(9),
+            Constraint::Min(0),
+        ],
+    )
+    .split(area);
+
+    let cancel_area = layout[1];
+    let accept_area = layout[3];
+
+    let cancel_button = Paragraph::new(" Cancel ")
+        .alignment(Alignment::Center)
+        .bold()
+        .bg(Color::Red)
+        .fg(DARK_TEXT);
+    let accept_button = Paragraph::new(" Replace ")
+        .alignment(Alignment::Center)
+        .bold()
+        .bg(Color::Blue)
+        .fg(Color::White);
+
+    app.input_capture
+        .click_mode_popup(&cancel_area, Replace(ReplaceAction::Deny));
+    f.render_widget(cancel_button, cancel_area);
+
+    app.input_capture
+        .click_mode_popup(&accept_area, Replace(ReplaceAction::Accept));
+    f.render_widget(accept_button, accept_area);
+}
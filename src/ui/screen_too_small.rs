@@ -6,7 +6,7 @@ use super::centered_box;
 
 pub fn show(f: &mut Frame) {
     let area = f.area();
-    let message = "Terminal must be 30x70!";
+    let message = "Terminal must be 35x70!";
     let (w, h) = (message.len() as _, message.lines().count() as _);
 
     let center = centered_box(w, h, area);
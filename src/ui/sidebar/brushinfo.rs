@@ -1,7 +1,7 @@
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
-use ratatui::style::{Style, Stylize};
+use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::{block::Title, Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::app::App;
@@ -13,7 +13,7 @@ use crate::components::clicks::SetValue::Reset;
 use super::{Button, DARK_TEXT, LIGHT_TEXT, TOOL_BORDER};
 
 pub fn render(app: &mut App, f: &mut Frame, area: Rect) {
-    let block_area = block(f, area);
+    let block_area = block(app, f, area);
 
     let brush_layout = Layout::new(
         Direction::Horizontal,
@@ -30,12 +30,23 @@ pub fn render(app: &mut App, f: &mut Frame, area: Rect) {
     render_char_info(app, f, brush_layout[2]);
 }
 
-fn block(f: &mut Frame, area: Rect) -> Rect {
-    let brush_block = Block::new()
+fn block(app: &mut App, f: &mut Frame, area: Rect) -> Rect {
+    let mut brush_block = Block::new()
         .title("Brush info ".bold())
         .borders(Borders::TOP)
         .border_style(Style::new().fg(TOOL_BORDER));
 
+    // The brush can't change the active layer as it is while it is locked
+    if let Some(lock) = app.layers.get_active_layer().lock_label() {
+        brush_block = brush_block.title(
+            Title::from(Span::styled(
+                lock,
+                Style::new().bg(Color::Red).fg(DARK_TEXT),
+            ))
+            .alignment(Alignment::Right),
+        );
+    }
+
     let inner_block = brush_block.inner(area);
 
     f.render_widget(brush_block, area);
@@ -17,12 +17,19 @@ pub fn render(app: &mut App, f: &mut Frame, area: Rect) {
     let block = outer_block(app, f, area);
     let layout = Layout::new(
         Direction::Vertical,
-        [Constraint::Max(1), Constraint::Min(0)],
+        [
+            Constraint::Max(1),
+            Constraint::Max(1),
+            Constraint::Max(1),
+            Constraint::Min(0),
+        ],
     )
     .split(block);
 
     render_buttons(app, f, layout[0]);
-    render_layers(app, f, layout[1]);
+    render_stack_buttons(app, f, layout[1]);
+    render_settings(app, f, layout[2]);
+    render_layers(app, f, layout[3]);
 }
 
 fn outer_block(app: &mut App, f: &mut Frame, area: Rect) -> Rect {
@@ -66,6 +73,58 @@ fn render_buttons(app: &mut App, f: &mut Frame, area: Rect) {
     down_button(app, f, row[3]);
 }
 
+/// Buttons that combine or copy layers
+fn render_stack_buttons(app: &mut App, f: &mut Frame, area: Rect) {
+    let row = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(9),
+            Constraint::Min(0),
+        ],
+    )
+    .split(area);
+
+    base_button(app, f, row[0], Duplicate, "Clone");
+    base_button(app, f, row[1], MergeDown, "Merge");
+    base_button(app, f, row[2], Flatten, "Flatten");
+}
+
+/// Blend mode and opacity of the active layer
+fn render_settings(app: &mut App, f: &mut Frame, area: Rect) {
+    let row = Layout::new(
+        Direction::Horizontal,
+        [
+            Constraint::Length(6),
+            Constraint::Length(7),
+            Constraint::Min(0),
+            Constraint::Length(7),
+            Constraint::Length(6),
+        ],
+    )
+    .split(area);
+
+    let index = app.layers.active as u8;
+    let layer = app.layers.get_active_layer();
+    let blend = layer.blend.to_string();
+    let opacity = format!("{}%", layer.opacity);
+
+    f.render_widget(Paragraph::new("Blend").fg(LIGHT_TEXT), row[0]);
+    app.input_capture
+        .click_mode_normal(&row[1], Layer(CycleBlend(index)));
+    f.render_widget(Paragraph::new(Line::from(Button::normal(&blend))), row[1]);
+
+    // Left click lowers the opacity and right click raises it
+    f.render_widget(Paragraph::new("Opacity").fg(LIGHT_TEXT), row[3]);
+    app.input_capture
+        .click_mode_normal(&row[4], Layer(Opacity(index)));
+    f.render_widget(
+        Paragraph::new(Line::from(Button::normal(&opacity))).alignment(Alignment::Right),
+        row[4],
+    );
+}
+
 fn render_layers(app: &mut App, f: &mut Frame, area: Rect) {
     let layers_count = app.layers.layers.len();
 
@@ -84,7 +143,7 @@ fn render_layers(app: &mut App, f: &mut Frame, area: Rect) {
 
     f.render_widget(Block::new().bg(BG_LAYER_MANAGER), rows[layers_count]);
 
-    for (i, (name, show)) in app.layers.get_display_info() {
+    for (i, layer) in app.layers.get_display_info() {
         let index = layers_count - (i + 1);
         let is_active_layer = index == app.layers.active;
 
@@ -99,23 +158,47 @@ fn render_layers(app: &mut App, f: &mut Frame, area: Rect) {
 
         let row = Layout::new(
             Direction::Horizontal,
-            [Constraint::Min(0), Constraint::Max(6)],
+            [
+                Constraint::Min(0),
+                Constraint::Max(3),
+                Constraint::Max(3),
+                Constraint::Max(6),
+            ],
         )
         .split(rows[i]);
 
         // Layer
-        f.render_widget(Paragraph::new(name), row[0]);
+        f.render_widget(Paragraph::new(layer.name.as_str()), row[0]);
+
+        // Lock and alpha lock, highlighted while they are on
+        app.input_capture
+            .click_mode_normal(&row[1], Layer(ToggleLock(index as u8)));
+        let btn = if layer.locked {
+            Button::selected("L")
+        } else {
+            Button::normal("L")
+        };
+        f.render_widget(Paragraph::new(Line::from(btn)), row[1]);
+
+        app.input_capture
+            .click_mode_normal(&row[2], Layer(ToggleAlphaLock(index as u8)));
+        let btn = if layer.alpha_locked {
+            Button::selected("α")
+        } else {
+            Button::normal("α")
+        };
+        f.render_widget(Paragraph::new(Line::from(btn)), row[2]);
 
         // Show/hide click register
         app.input_capture
-            .click_mode_normal(&row[1], Layer(ToggleVis(index as u8)));
+            .click_mode_normal(&row[3], Layer(ToggleVis(index as u8)));
 
-        let btn = if show {
+        let btn = if layer.visible {
             Button::normal("Hide")
         } else {
             Button::selected("Show")
         };
-        f.render_widget(Paragraph::new(Line::from(btn)), row[1]);
+        f.render_widget(Paragraph::new(Line::from(btn)), row[3]);
     }
 }
 
@@ -33,12 +33,12 @@ pub fn render(app: &mut App, f: &mut Frame, area: Rect) {
     let bar_layout = Layout::new(
         Direction::Vertical,
         [
-            Constraint::Max(3),  // 0: Brush info
-            Constraint::Max(4),  // 1: Tools
-            Constraint::Max(10), // 2: Char picker
-            Constraint::Max(6),  // 3: Palette
-            Constraint::Min(0),  // 4: Layers
-            Constraint::Max(1),  // 5: Help text
+            Constraint::Max(3),                 // 0: Brush info
+            Constraint::Max(toolbox::height()), // 1: Tools
+            Constraint::Max(10),                // 2: Char picker
+            Constraint::Max(6),                 // 3: Palette
+            Constraint::Min(0),                 // 4: Layers
+            Constraint::Max(1),                 // 5: Help text
         ],
     )
     .split(bar_inner);
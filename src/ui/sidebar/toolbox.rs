@@ -1,19 +1,38 @@
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
-use ratatui::style::{Style, Stylize};
+use ratatui::style::{Color, Style, Stylize};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::block::{Position, Title};
 use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::app::App;
-use crate::components::clicks::{ClickAction::Set, SetValue::Tool};
+use crate::components::clicks::ClickAction::{Next, Set};
+use crate::components::clicks::{Increment::ToolOption, SetValue::Tool};
+use crate::components::input::InputMode;
 use crate::components::tools::Tools;
 
-use super::{Button, LIGHT_TEXT, TOOL_BORDER};
+use super::{Button, ACCENT_BUTTON_COLOR, DARK_TEXT, LIGHT_TEXT, TOOL_BORDER};
+
+const TOOLS_PER_ROW: usize = 9;
+
+/// Rows of tool buttons
+fn button_rows() -> u16 {
+    Tools::all().len().div_ceil(TOOLS_PER_ROW) as u16
+}
+
+/// Height of the toolbox including the borders and the info line
+pub fn height() -> u16 {
+    button_rows() + 3
+}
 
 pub fn render(app: &mut App, f: &mut Frame, area: Rect) {
     let block_area = outer_block(f, area);
-    let rows = Layout::new(Direction::Vertical, [Constraint::Min(1); 2]).split(block_area);
+    let button_rows = button_rows();
+    let rows = Layout::new(
+        Direction::Vertical,
+        [Constraint::Length(button_rows), Constraint::Min(1)],
+    )
+    .split(block_area);
     render_buttons(app, f, rows[0]);
     render_info(app, f, rows[1]);
 }
@@ -40,33 +59,94 @@ fn outer_block(f: &mut Frame, area: Rect) -> Rect {
 fn render_buttons(app: &mut App, f: &mut Frame, area: Rect) {
     let current_tool = app.brush.tool;
     let tools = Tools::all();
-    let tool_amount = tools.len();
 
-    let row = Layout::new(Direction::Horizontal, vec![Constraint::Min(3); tool_amount]).split(area);
+    let rows = Layout::new(
+        Direction::Vertical,
+        vec![Constraint::Length(1); button_rows().into()],
+    )
+    .split(area);
+    let row = Layout::new(Direction::Horizontal, [Constraint::Min(3); TOOLS_PER_ROW]);
+    let button_areas: Vec<Rect> = rows.iter().flat_map(|&r| row.split(r).to_vec()).collect();
 
-    tools.iter().zip(row.iter()).for_each(|(&t, &area)| {
-        let c = t.char();
+    tools
+        .iter()
+        .zip(button_areas.iter())
+        .for_each(|(&t, &area)| {
+            let c = t.char();
 
-        let btn = if current_tool == t {
-            Button::selected(&c)
-        } else {
-            Button::normal(&c)
-        };
+            let btn = if current_tool == t {
+                Button::selected(&c)
+            } else {
+                Button::normal(&c)
+            };
+
+            let button = Paragraph::new(Line::from(btn));
+
+            app.input_capture.click_mode_normal(&area, Set(Tool(t)));
+            f.render_widget(button, area);
+        });
+}
+
+fn render_info(app: &mut App, f: &mut Frame, area: Rect) {
+    if app.brush.tool == Tools::Gradient && app.brush.has_unset_color() {
+        let warning = Paragraph::new("Gradient needs FG & BG")
+            .fg(Color::Red)
+            .alignment(Alignment::Center);
+        f.render_widget(warning, area);
+        return;
+    }
+
+    let options = app.option_labels();
+
+    if options.is_empty() {
+        let info = Paragraph::new(Line::from(vec![
+            Span::from("Current tool: "),
+            Span::from(app.brush.tool.to_string()).bold(),
+        ]))
+        .fg(LIGHT_TEXT)
+        .alignment(Alignment::Center);
+
+        f.render_widget(info, area);
+        return;
+    }
 
-        let button = Paragraph::new(Line::from(btn));
+    let mut constraints = vec![Constraint::Min(0)];
+    constraints.extend(
+        options
+            .iter()
+            .map(|label| Constraint::Length(label.chars().count() as u16 + 2)),
+    );
+    let layout = Layout::new(Direction::Horizontal, constraints).split(area);
 
-        app.input_capture.click_mode_normal(&area, Set(Tool(t)));
-        f.render_widget(button, area);
-    });
+    let info = Paragraph::new(Span::from(app.brush.tool.to_string()).bold()).fg(LIGHT_TEXT);
+    f.render_widget(info, layout[0]);
+
+    for (i, (label, &area)) in options.iter().zip(layout.iter().skip(1)).enumerate() {
+        let option_button = match (i, app.input_capture.mode) {
+            (0, InputMode::Ramp) => ramp_field(app),
+            _ => Paragraph::new(Line::from(Button::accent(label))),
+        };
+
+        app.input_capture
+            .click_mode_normal(&area, Next(ToolOption(i as u8)));
+        f.render_widget(option_button, area);
+    }
 }
 
-fn render_info(app: &App, f: &mut Frame, area: Rect) {
-    let info = Paragraph::new(Line::from(vec![
-        Span::from("Current tool: "),
-        Span::from(app.brush.tool.to_string()).bold(),
-    ]))
-    .fg(LIGHT_TEXT)
-    .alignment(Alignment::Center);
+/// The ramp of the shade tool being typed in, the glyph after the caret is highlighted
+fn ramp_field(app: &App) -> Paragraph<'static> {
+    let mut spans = vec![Span::raw("▐").fg(ACCENT_BUTTON_COLOR)];
+    spans.extend(app.ramp.iter().chain([&' ']).enumerate().map(|(i, c)| {
+        let span = Span::raw(c.to_string())
+            .bg(ACCENT_BUTTON_COLOR)
+            .fg(DARK_TEXT);
+        if i == app.ramp_caret {
+            span.reversed()
+        } else {
+            span
+        }
+    }));
+    spans.push(Span::raw("▌").fg(ACCENT_BUTTON_COLOR));
 
-    f.render_widget(info, area);
+    Paragraph::new(Line::from(spans))
 }